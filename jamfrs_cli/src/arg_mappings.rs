@@ -1,136 +1,271 @@
 use crate::args::*;
-use jamfrs_lib::api_service::api_endpoints::{ApiEndpoints, Args, CommandDetails};
+use jamfrs_lib::api_service::api_endpoints::{
+    ApiEndpoints, Args, CommandDetails, Identifier, RequestBody,
+};
+use jamfrs_lib::api_service::filter::Filter;
+use jamfrs_lib::api_service::pagination::{PageQuery, DEFAULT_PAGE_SIZE};
 use std::collections::HashMap;
 
 pub fn get_command_details(entity_type: &EntityType) -> Result<CommandDetails, String> {
     let mut args_map: HashMap<&str, String> = HashMap::new();
+    let mut body = None;
+    let mut list_options = None;
+    let mut subset: &[String] = &[];
 
     let (args, endpoint) = match &entity_type {
         EntityType::Computer(command) => match &command.subcommand {
-            ComputerSubcommand::Delete(id) => {
-                (Args::Ids(id.get_ids()?), ApiEndpoints::ComputerDelete)
+            ComputerSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::ComputerCreate)
+            }
+            ComputerSubcommand::Delete(id) => (id.args()?, ApiEndpoints::ComputerDelete),
+            ComputerSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::ComputerUpdate)
+            }
+            ComputerSubcommand::Show(show) => {
+                subset = &show.subset;
+                (show.id.args()?, ApiEndpoints::ComputerShow)
             }
-            ComputerSubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::ComputerShow),
             ComputerSubcommand::Search { search_query } => {
-                args_map.insert("{search_query}", search_query.clone());
+                args_map.insert("search_query", search_query.clone());
                 (Args::Strings(args_map), ApiEndpoints::ComputerSearch)
             }
-            ComputerSubcommand::List => (Args::None, ApiEndpoints::ComputerList),
+            ComputerSubcommand::List(options) => {
+                list_options = Some(options);
+                (Args::None, ApiEndpoints::ComputerList)
+            }
         },
         EntityType::Mobile(command) => match &command.subcommand {
-            MobileSubcommand::Delete(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::MobileDelete),
-            MobileSubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::MobileShow),
+            MobileSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::MobileCreate)
+            }
+            MobileSubcommand::Delete(id) => (id.args()?, ApiEndpoints::MobileDelete),
+            MobileSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::MobileUpdate)
+            }
+            MobileSubcommand::Show(show) => {
+                subset = &show.subset;
+                (show.id.args()?, ApiEndpoints::MobileShow)
+            }
             MobileSubcommand::Search { search_query } => {
-                args_map.insert("{search_query}", search_query.clone());
+                args_map.insert("search_query", search_query.clone());
                 (Args::Strings(args_map), ApiEndpoints::MobileSearch)
             }
-            MobileSubcommand::List => (Args::None, ApiEndpoints::MobileList),
+            MobileSubcommand::List(options) => {
+                list_options = Some(options);
+                (Args::None, ApiEndpoints::MobileList)
+            }
         },
         EntityType::User(command) => match &command.subcommand {
-            UserSubcommand::Delete(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::UserDelete),
-            UserSubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::UserShow),
+            UserSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::UserCreate)
+            }
+            UserSubcommand::Delete(id) => (id.args()?, ApiEndpoints::UserDelete),
+            UserSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::UserUpdate)
+            }
+            UserSubcommand::Show(id) => (id.args()?, ApiEndpoints::UserShow),
             UserSubcommand::List => (Args::None, ApiEndpoints::UserList),
         },
         EntityType::Policy(command) => match &command.subcommand {
-            PolicySubcommand::Delete(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::PolicyDelete),
-            PolicySubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::PolicyShow),
+            PolicySubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::PolicyCreate)
+            }
+            PolicySubcommand::Delete(id) => (id.args()?, ApiEndpoints::PolicyDelete),
+            PolicySubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::PolicyUpdate)
+            }
+            PolicySubcommand::Show(id) => (id.args()?, ApiEndpoints::PolicyShow),
             PolicySubcommand::List => (Args::None, ApiEndpoints::PolicyList),
         },
         EntityType::Package(command) => match &command.subcommand {
-            PackageSubcommand::Delete(id) => {
-                (Args::Ids(id.get_ids()?), ApiEndpoints::PackageDelete)
+            PackageSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::PackageCreate)
+            }
+            PackageSubcommand::Delete(id) => (id.args()?, ApiEndpoints::PackageDelete),
+            PackageSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::PackageUpdate)
+            }
+            PackageSubcommand::Show(id) => (id.args()?, ApiEndpoints::PackageShow),
+            PackageSubcommand::List(options) => {
+                list_options = Some(options);
+                (Args::None, ApiEndpoints::PackageList)
             }
-            PackageSubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::PackageShow),
-            PackageSubcommand::List => (Args::None, ApiEndpoints::PackageList),
         },
         EntityType::Category(command) => match &command.subcommand {
-            CategorySubcommand::Delete(id) => {
-                (Args::Ids(id.get_ids()?), ApiEndpoints::CategoryDelete)
+            CategorySubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::CategoryCreate)
+            }
+            CategorySubcommand::Delete(id) => (id.args()?, ApiEndpoints::CategoryDelete),
+            CategorySubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::CategoryUpdate)
+            }
+            CategorySubcommand::Show(id) => (id.args()?, ApiEndpoints::CategoryShow),
+            CategorySubcommand::List(options) => {
+                list_options = Some(options);
+                (Args::None, ApiEndpoints::CategoryList)
             }
-            CategorySubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::CategoryShow),
-            CategorySubcommand::List => (Args::None, ApiEndpoints::CategoryList),
         },
         EntityType::Department(command) => match &command.subcommand {
-            DepartmentSubcommand::Delete(id) => {
-                (Args::Ids(id.get_ids()?), ApiEndpoints::DepartmentDelete)
+            DepartmentSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::DepartmentCreate)
             }
-            DepartmentSubcommand::Show(id) => {
-                (Args::Ids(id.get_ids()?), ApiEndpoints::DepartmentShow)
+            DepartmentSubcommand::Delete(id) => (id.args()?, ApiEndpoints::DepartmentDelete),
+            DepartmentSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::DepartmentUpdate)
+            }
+            DepartmentSubcommand::Show(id) => (id.args()?, ApiEndpoints::DepartmentShow),
+            DepartmentSubcommand::List(options) => {
+                list_options = Some(options);
+                (Args::None, ApiEndpoints::DepartmentList)
             }
-            DepartmentSubcommand::List => (Args::None, ApiEndpoints::DepartmentList),
         },
         EntityType::Ebook(command) => match &command.subcommand {
-            EbookSubcommand::Delete(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::EbookDelete),
-            EbookSubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::EbookShow),
+            EbookSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::EbookCreate)
+            }
+            EbookSubcommand::Delete(id) => (id.args()?, ApiEndpoints::EbookDelete),
+            EbookSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::EbookUpdate)
+            }
+            EbookSubcommand::Show(id) => (id.args()?, ApiEndpoints::EbookShow),
             EbookSubcommand::List => (Args::None, ApiEndpoints::EbookList),
         },
         EntityType::Building(command) => match &command.subcommand {
-            BuildingSubcommand::Delete(id) => {
-                (Args::Ids(id.get_ids()?), ApiEndpoints::BuildingDelete)
+            BuildingSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::BuildingCreate)
+            }
+            BuildingSubcommand::Delete(id) => (id.args()?, ApiEndpoints::BuildingDelete),
+            BuildingSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::BuildingUpdate)
+            }
+            BuildingSubcommand::Show(id) => (id.args()?, ApiEndpoints::BuildingShow),
+            BuildingSubcommand::List(options) => {
+                list_options = Some(options);
+                (Args::None, ApiEndpoints::BuildingList)
             }
-            BuildingSubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::BuildingShow),
-            BuildingSubcommand::List => (Args::None, ApiEndpoints::BuildingList),
         },
         EntityType::MacApp(command) => match &command.subcommand {
-            MacAppSubcommand::Delete(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::MacAppDelete),
-            MacAppSubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::MacAppShow),
+            MacAppSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::MacAppCreate)
+            }
+            MacAppSubcommand::Delete(id) => (id.args()?, ApiEndpoints::MacAppDelete),
+            MacAppSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::MacAppUpdate)
+            }
+            MacAppSubcommand::Show(id) => (id.args()?, ApiEndpoints::MacAppShow),
             MacAppSubcommand::List => (Args::None, ApiEndpoints::MacAppList),
         },
         EntityType::MobileApp(command) => match &command.subcommand {
-            MobileAppSubcommand::Delete(id) => {
-                (Args::Ids(id.get_ids()?), ApiEndpoints::MobileAppDelete)
+            MobileAppSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::MobileAppCreate)
             }
-            MobileAppSubcommand::Show(id) => {
-                (Args::Ids(id.get_ids()?), ApiEndpoints::MobileAppShow)
+            MobileAppSubcommand::Delete(id) => (id.args()?, ApiEndpoints::MobileAppDelete),
+            MobileAppSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::MobileAppUpdate)
             }
+            MobileAppSubcommand::Show(id) => (id.args()?, ApiEndpoints::MobileAppShow),
             MobileAppSubcommand::List => (Args::None, ApiEndpoints::MobileAppList),
         },
         EntityType::Script(command) => match &command.subcommand {
-            ScriptSubcommand::Delete(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::ScriptDelete),
-            ScriptSubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::ScriptShow),
-            ScriptSubcommand::List => (Args::None, ApiEndpoints::ScriptList),
+            ScriptSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::ScriptCreate)
+            }
+            ScriptSubcommand::Delete(id) => (id.args()?, ApiEndpoints::ScriptDelete),
+            ScriptSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::ScriptUpdate)
+            }
+            ScriptSubcommand::Show(id) => (id.args()?, ApiEndpoints::ScriptShow),
+            ScriptSubcommand::List(options) => {
+                list_options = Some(options);
+                (Args::None, ApiEndpoints::ScriptList)
+            }
         },
         EntityType::RestrictedSoftware(command) => match &command.subcommand {
-            RestrictedSoftwareSubcommand::Delete(id) => (
-                Args::Ids(id.get_ids()?),
-                ApiEndpoints::RestrictedSoftwareDelete,
-            ),
-            RestrictedSoftwareSubcommand::Show(id) => (
-                Args::Ids(id.get_ids()?),
-                ApiEndpoints::RestrictedSoftwareShow,
-            ),
+            RestrictedSoftwareSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::RestrictedSoftwareCreate)
+            }
+            RestrictedSoftwareSubcommand::Delete(id) => {
+                (id.args()?, ApiEndpoints::RestrictedSoftwareDelete)
+            }
+            RestrictedSoftwareSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::RestrictedSoftwareUpdate)
+            }
+            RestrictedSoftwareSubcommand::Show(id) => {
+                (id.args()?, ApiEndpoints::RestrictedSoftwareShow)
+            }
             RestrictedSoftwareSubcommand::List => {
                 (Args::None, ApiEndpoints::RestrictedSoftwareList)
             }
         },
         EntityType::Printer(command) => match &command.subcommand {
-            PrinterSubcommand::Delete(id) => {
-                (Args::Ids(id.get_ids()?), ApiEndpoints::PrinterDelete)
+            PrinterSubcommand::Create(document) => {
+                body = Some(RequestBody::new(document.read()?));
+                (Args::None, ApiEndpoints::PrinterCreate)
+            }
+            PrinterSubcommand::Delete(id) => (id.args()?, ApiEndpoints::PrinterDelete),
+            PrinterSubcommand::Update(update) => {
+                body = Some(RequestBody::new(update.document.read()?));
+                (update.id.args()?, ApiEndpoints::PrinterUpdate)
             }
-            PrinterSubcommand::Show(id) => (Args::Ids(id.get_ids()?), ApiEndpoints::PrinterShow),
+            PrinterSubcommand::Show(id) => (id.args()?, ApiEndpoints::PrinterShow),
             PrinterSubcommand::List => (Args::None, ApiEndpoints::PrinterList),
         },
         EntityType::Patch(command) => match &command.patch_command {
             PatchSubcommand::Policy(policy_subcommand) => match &policy_subcommand {
-                PatchPolicyCommand::Delete(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::PatchPolicyDelete)
+                PatchPolicyCommand::Create {
+                    software_title_id,
+                    document,
+                } => {
+                    body = Some(RequestBody::new(document.read()?));
+                    (
+                        Args::Ids(vec![software_title_id.clone()]),
+                        ApiEndpoints::PatchPolicyCreate,
+                    )
                 }
-                PatchPolicyCommand::Show(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::PatchPolicyShow)
+                PatchPolicyCommand::Delete(id) => (id.args()?, ApiEndpoints::PatchPolicyDelete),
+                PatchPolicyCommand::Update(update) => {
+                    body = Some(RequestBody::new(update.document.read()?));
+                    (update.id.args()?, ApiEndpoints::PatchPolicyUpdate)
                 }
+                PatchPolicyCommand::Show(id) => (id.args()?, ApiEndpoints::PatchPolicyShow),
                 PatchPolicyCommand::List => (Args::None, ApiEndpoints::PatchPolicyList),
             },
             PatchSubcommand::Report(command) => match &command {
-                PatchReportCommand::ListSoftware(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::PatchReportListSoftware,
-                ),
+                PatchReportCommand::ListSoftware(id) => {
+                    (id.args()?, ApiEndpoints::PatchReportListSoftware)
+                }
                 PatchReportCommand::ListComputer {
                     id,
                     software_version,
                 } => {
-                    args_map.insert("{id}", id.clone());
-                    args_map.insert("{software_version}", software_version.clone());
+                    args_map.insert("id", id.clone());
+                    args_map.insert("software_version", software_version.clone());
                     (
                         Args::Strings(args_map),
                         ApiEndpoints::PatchReportListComputer,
@@ -138,42 +273,52 @@ pub fn get_command_details(entity_type: &EntityType) -> Result<CommandDetails, S
                 }
             },
             PatchSubcommand::SoftwareTitles(command) => match &command {
-                PatchSoftwareTitleCommand::Delete(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::PatchSoftwareTitleDelete,
-                ),
-                PatchSoftwareTitleCommand::Show(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::PatchSoftwareTitleShow,
-                ),
+                PatchSoftwareTitleCommand::Create(document) => {
+                    body = Some(RequestBody::new(document.read()?));
+                    (Args::None, ApiEndpoints::PatchSoftwareTitleCreate)
+                }
+                PatchSoftwareTitleCommand::Delete(id) => {
+                    (id.args()?, ApiEndpoints::PatchSoftwareTitleDelete)
+                }
+                PatchSoftwareTitleCommand::Update(update) => {
+                    body = Some(RequestBody::new(update.document.read()?));
+                    (update.id.args()?, ApiEndpoints::PatchSoftwareTitleUpdate)
+                }
+                PatchSoftwareTitleCommand::Show(id) => {
+                    (id.args()?, ApiEndpoints::PatchSoftwareTitleShow)
+                }
                 PatchSoftwareTitleCommand::List => {
                     (Args::None, ApiEndpoints::PatchSoftwareTitleList)
                 }
             },
             PatchSubcommand::AvailableTitles(command) => match &command {
-                PatchAvailableTitleCommand::List(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::PatchAvailableTitleList,
-                ),
+                PatchAvailableTitleCommand::List(id) => {
+                    (id.args()?, ApiEndpoints::PatchAvailableTitleList)
+                }
             },
             PatchSubcommand::ExternalSources(command) => match &command {
-                PatchExternalSourceCommand::Delete(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::PatchExternalSourceDelete,
-                ),
-                PatchExternalSourceCommand::Show(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::PatchExternalSourceShow,
-                ),
+                PatchExternalSourceCommand::Create(document) => {
+                    body = Some(RequestBody::new(document.read()?));
+                    (Args::None, ApiEndpoints::PatchExternalSourceCreate)
+                }
+                PatchExternalSourceCommand::Delete(id) => {
+                    (id.args()?, ApiEndpoints::PatchExternalSourceDelete)
+                }
+                PatchExternalSourceCommand::Update(update) => {
+                    body = Some(RequestBody::new(update.document.read()?));
+                    (update.id.args()?, ApiEndpoints::PatchExternalSourceUpdate)
+                }
+                PatchExternalSourceCommand::Show(id) => {
+                    (id.args()?, ApiEndpoints::PatchExternalSourceShow)
+                }
                 PatchExternalSourceCommand::List => {
                     (Args::None, ApiEndpoints::PatchExternalSourceList)
                 }
             },
             PatchSubcommand::InternalSources(command) => match &command {
-                PatchInternalSourceCommand::Show(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::PatchInternalSourceShow,
-                ),
+                PatchInternalSourceCommand::Show(id) => {
+                    (id.args()?, ApiEndpoints::PatchInternalSourceShow)
+                }
                 PatchInternalSourceCommand::List => {
                     (Args::None, ApiEndpoints::PatchInternalSourceList)
                 }
@@ -181,69 +326,159 @@ pub fn get_command_details(entity_type: &EntityType) -> Result<CommandDetails, S
         },
         EntityType::Group(command) => match &command.group_command {
             GroupSubcommand::Computer(group_subcommand) => match &group_subcommand {
-                ComputerGroupCommand::Delete(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::GroupComputerDelete)
+                ComputerGroupCommand::Create(document) => {
+                    body = Some(RequestBody::new(document.read()?));
+                    (Args::None, ApiEndpoints::GroupComputerCreate)
                 }
-                ComputerGroupCommand::Show(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::GroupComputerShow)
+                ComputerGroupCommand::Delete(id) => (id.args()?, ApiEndpoints::GroupComputerDelete),
+                ComputerGroupCommand::Update(update) => {
+                    body = Some(RequestBody::new(update.document.read()?));
+                    (update.id.args()?, ApiEndpoints::GroupComputerUpdate)
                 }
+                ComputerGroupCommand::Show(id) => (id.args()?, ApiEndpoints::GroupComputerShow),
                 ComputerGroupCommand::List => (Args::None, ApiEndpoints::GroupComputerList),
             },
             GroupSubcommand::Mobile(group_subcommand) => match &group_subcommand {
-                MobileGroupCommand::Delete(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::GroupMobileDelete)
+                MobileGroupCommand::Create(document) => {
+                    body = Some(RequestBody::new(document.read()?));
+                    (Args::None, ApiEndpoints::GroupMobileCreate)
                 }
-                MobileGroupCommand::Show(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::GroupMobileShow)
+                MobileGroupCommand::Delete(id) => (id.args()?, ApiEndpoints::GroupMobileDelete),
+                MobileGroupCommand::Update(update) => {
+                    body = Some(RequestBody::new(update.document.read()?));
+                    (update.id.args()?, ApiEndpoints::GroupMobileUpdate)
                 }
+                MobileGroupCommand::Show(id) => (id.args()?, ApiEndpoints::GroupMobileShow),
                 MobileGroupCommand::List => (Args::None, ApiEndpoints::GroupMobileList),
             },
             GroupSubcommand::User(group_subcommand) => match &group_subcommand {
-                UserGroupCommand::Delete(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::GroupUserDelete)
+                UserGroupCommand::Create(document) => {
+                    body = Some(RequestBody::new(document.read()?));
+                    (Args::None, ApiEndpoints::GroupUserCreate)
                 }
-                UserGroupCommand::Show(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::GroupUserShow)
+                UserGroupCommand::Delete(id) => (id.args()?, ApiEndpoints::GroupUserDelete),
+                UserGroupCommand::Update(update) => {
+                    body = Some(RequestBody::new(update.document.read()?));
+                    (update.id.args()?, ApiEndpoints::GroupUserUpdate)
                 }
+                UserGroupCommand::Show(id) => (id.args()?, ApiEndpoints::GroupUserShow),
                 UserGroupCommand::List => (Args::None, ApiEndpoints::GroupUserList),
             },
         },
         EntityType::AdvSearch(command) => match &command.adv_search_command {
             AdvSearchSubcommand::Computer(adv_search_subcommand) => match &adv_search_subcommand {
-                ComputerAdvSearchCommand::Delete(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::AdvSearchComputerDelete,
-                ),
-                ComputerAdvSearchCommand::Show(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::AdvSearchComputerShow,
-                ),
+                ComputerAdvSearchCommand::Create(document) => {
+                    body = Some(RequestBody::new(document.read()?));
+                    (Args::None, ApiEndpoints::AdvSearchComputerCreate)
+                }
+                ComputerAdvSearchCommand::Delete(id) => {
+                    (id.args()?, ApiEndpoints::AdvSearchComputerDelete)
+                }
+                ComputerAdvSearchCommand::Update(update) => {
+                    body = Some(RequestBody::new(update.document.read()?));
+                    (update.id.args()?, ApiEndpoints::AdvSearchComputerUpdate)
+                }
+                ComputerAdvSearchCommand::Show(id) => {
+                    (id.args()?, ApiEndpoints::AdvSearchComputerShow)
+                }
                 ComputerAdvSearchCommand::List => (Args::None, ApiEndpoints::AdvSearchComputerList),
             },
             AdvSearchSubcommand::Mobile(adv_search_subcommand) => match &adv_search_subcommand {
-                MobileAdvSearchCommand::Delete(id) => (
-                    Args::Ids(id.get_ids()?),
-                    ApiEndpoints::AdvSearchMobileDelete,
-                ),
-                MobileAdvSearchCommand::Show(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::AdvSearchMobileShow)
+                MobileAdvSearchCommand::Create(document) => {
+                    body = Some(RequestBody::new(document.read()?));
+                    (Args::None, ApiEndpoints::AdvSearchMobileCreate)
+                }
+                MobileAdvSearchCommand::Delete(id) => {
+                    (id.args()?, ApiEndpoints::AdvSearchMobileDelete)
                 }
+                MobileAdvSearchCommand::Update(update) => {
+                    body = Some(RequestBody::new(update.document.read()?));
+                    (update.id.args()?, ApiEndpoints::AdvSearchMobileUpdate)
+                }
+                MobileAdvSearchCommand::Show(id) => (id.args()?, ApiEndpoints::AdvSearchMobileShow),
                 MobileAdvSearchCommand::List => (Args::None, ApiEndpoints::AdvSearchMobileList),
             },
             AdvSearchSubcommand::User(adv_search_subcommand) => match &adv_search_subcommand {
-                UserAdvSearchCommand::Delete(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::AdvSearchUserDelete)
+                UserAdvSearchCommand::Create(document) => {
+                    body = Some(RequestBody::new(document.read()?));
+                    (Args::None, ApiEndpoints::AdvSearchUserCreate)
                 }
-                UserAdvSearchCommand::Show(id) => {
-                    (Args::Ids(id.get_ids()?), ApiEndpoints::AdvSearchUserShow)
+                UserAdvSearchCommand::Delete(id) => (id.args()?, ApiEndpoints::AdvSearchUserDelete),
+                UserAdvSearchCommand::Update(update) => {
+                    body = Some(RequestBody::new(update.document.read()?));
+                    (update.id.args()?, ApiEndpoints::AdvSearchUserUpdate)
                 }
+                UserAdvSearchCommand::Show(id) => (id.args()?, ApiEndpoints::AdvSearchUserShow),
                 UserAdvSearchCommand::List => (Args::None, ApiEndpoints::AdvSearchUserList),
             },
         },
+        EntityType::Export(_) | EntityType::Restore(_) => {
+            return Err(String::from(
+                "Exports and restores aren't sent as a single command",
+            ));
+        }
+    };
+
+    let endpoint = match entity_type.api() {
+        Api::Classic => endpoint,
+        Api::Pro => endpoint
+            .jamf_pro_equivalent()
+            .ok_or("This command isn't available in the Jamf Pro API; Use --api classic instead")?,
+    };
+
+    let endpoint = endpoint.usage();
+
+    if let Args::Lookup(identifier, _) = &args {
+        if !endpoint.supports_identifier(*identifier) {
+            return Err(format!(
+                "This command can't look records up by {}; Use an Id instead",
+                match identifier {
+                    Identifier::Name => "name",
+                    Identifier::SerialNumber => "serial number",
+                    Identifier::Udid => "UDID",
+                    Identifier::MacAddress => "MAC address",
+                }
+            ));
+        }
+    }
+
+    let subset = subset
+        .iter()
+        .map(|name| match endpoint.subsets() {
+            [] => Err("--subset is only supported with --api classic".to_string()),
+            subsets => endpoint.subset(name).ok_or(format!(
+                "Unknown subset '{name}'; Expected one of {}",
+                subsets.join(", ")
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Jamf Pro API lists are paginated, Classic API lists always return every record at once
+    let args = match list_options {
+        Some(options) if endpoint.is_jamf_pro_api() => Args::Pages(PageQuery {
+            page_size: options.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+            sort: options.sort.clone(),
+            filter: match options.filter.len() {
+                0 => None,
+                1 => Some(options.filter[0].to_string()),
+                _ if options.any => Some(Filter::any(options.filter.clone()).to_string()),
+                _ => Some(Filter::all(options.filter.clone()).to_string()),
+            },
+            limit: options.limit,
+        }),
+        Some(options) if options.is_set() => {
+            return Err(
+                "--page-size, --sort, --limit and --filter are only supported with --api pro"
+                    .to_string(),
+            )
+        }
+        _ => args,
     };
 
     Ok(CommandDetails {
         args,
-        endpoint: endpoint.usage(),
+        endpoint,
+        body,
+        subset,
     })
 }
@@ -1,4 +1,11 @@
-use clap::{Args, Parser, Subcommand};
+use crate::output::OutputFormat;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use jamfrs_lib::api_service::api_endpoints::{Args as ApiArgs, Identifier};
+use jamfrs_lib::api_service::filter::Filter;
+use jamfrs_lib::api_service::select::Selector;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
@@ -13,18 +20,45 @@ pub struct JamfrsArgs {
     pub port: Option<u16>,
 
     /// Username used for API calls
-    #[arg(short, long = "user", env = "JAMFRS_USERNAME")]
-    pub username: String,
+    #[arg(
+        short,
+        long = "user",
+        env = "JAMFRS_USERNAME",
+        required_unless_present = "client_id",
+        conflicts_with = "client_id",
+        requires = "password"
+    )]
+    pub username: Option<String>,
 
     /// Password used by API user
-    #[arg(short, long, env = "JAMFRS_PASSWORD")]
-    pub password: String,
+    #[arg(short, long, env = "JAMFRS_PASSWORD", requires = "username")]
+    pub password: Option<String>,
+
+    /// Client ID of a Jamf Pro API client; Use in place of --user and --password
+    #[arg(long, env = "JAMFRS_CLIENT_ID", requires = "client_secret")]
+    pub client_id: Option<String>,
+
+    /// Client secret of a Jamf Pro API client
+    #[arg(long, env = "JAMFRS_CLIENT_SECRET", requires = "client_id")]
+    pub client_secret: Option<String>,
 
     /// Pretty print output
     #[arg(long)]
     pub pretty: bool,
 
-    /// Request JSON data instead of the default XML; Note that delete queries always respond with XML
+    /// Format of the output; Every format but xml requests JSON records. Defaults to the records as they're returned, separated by commas
+    #[arg(long, value_enum)]
+    pub output: Option<OutputFormat>,
+
+    /// Fields written by --output csv and table, e.g. id,name,serial_number; Nested fields are named with dots, e.g. general.name. Defaults to every field
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+
+    /// Print only the values at a dotted path in each response, e.g. general.serial_number; Works the same for XML and JSON responses
+    #[arg(long, global = true)]
+    pub select: Option<Selector>,
+
+    /// Request JSON data instead of the default XML; XML responses, such as those to delete queries, are converted to JSON
     #[arg(long)]
     pub json: bool,
 
@@ -36,6 +70,39 @@ pub struct JamfrsArgs {
     #[arg(short, long, default_value = "false")]
     pub confirm: bool,
 
+    /// Maximum number of requests sent to the server at once
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..))]
+    pub concurrency: u16,
+
+    /// Number of times a GET or PUT is retried after a 429, 502, 503 or 504 response or a connection failure
+    #[arg(long, default_value_t = 3)]
+    pub retries: u32,
+
+    /// Also retry DELETE calls; Defaults to False
+    #[arg(long)]
+    pub retry_deletes: bool,
+
+    /// Print results in the order the Ids, names, etc. were given rather than as they arrive
+    #[arg(long)]
+    pub ordered: bool,
+
+    /// Print the requests that would be sent, with the bearer token redacted, without authenticating or sending anything
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    /// Directory records are backed up to before they're deleted, in a timestamped directory for each run
+    #[arg(
+        long,
+        global = true,
+        env = "JAMFRS_BACKUP_DIR",
+        default_value = "jamfrs-backups"
+    )]
+    pub backup_dir: PathBuf,
+
+    /// Delete records without backing them up first
+    #[arg(long, global = true)]
+    pub no_backup: bool,
+
     #[command(subcommand)]
     pub entity_type: EntityType,
 }
@@ -56,6 +123,8 @@ pub enum EntityType {
     Ebook(EbookCommand),
     /// Work with smart & static groups
     Group(GroupCommand),
+    /// Save every record of some or all kinds to a directory
+    Export(ExportArgs),
     /// Work with mac applications
     MacApp(MacAppCommand),
     /// Work with mobile device records
@@ -72,48 +141,114 @@ pub enum EntityType {
     Printer(PrinterCommand),
     /// Work with restricted software
     RestrictedSoftware(RestrictedSoftwareCommand),
+    /// Recreate records from backups or saved Classic API documents
+    Restore(RestoreArgs),
     /// Work with scripts
     Script(ScriptCommand),
     /// Work with users records
     User(UserCommand),
 }
 
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Export every kind of record
+    #[arg(long, required_unless_present = "entities")]
+    pub all: bool,
+
+    /// Kind of record to export, named as in the Classic API, e.g. policies; May be repeated
+    #[arg(long = "entity", conflicts_with = "all")]
+    pub entities: Vec<String>,
+
+    /// Directory records are written to, in a directory for each kind, e.g. policies/12-Install Chrome.xml
+    pub dir: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct RestoreArgs {
+    /// Document or directory of documents to restore, laid out as backups are, e.g. policies/12.xml
+    pub path: PathBuf,
+
+    /// Point references to restored records, e.g. a policy's packages, at their new ids
+    #[arg(long)]
+    pub rewrite_references: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Api {
+    /// Classic API (/JSSResource)
+    Classic,
+    /// Jamf Pro API (/api)
+    Pro,
+}
+
+impl EntityType {
+    /// API selected for the command; Entities that only exist in the Classic API always use it
+    pub fn api(&self) -> Api {
+        match self {
+            EntityType::Computer(command) => command.api,
+            EntityType::Mobile(command) => command.api,
+            EntityType::Package(command) => command.api,
+            EntityType::Category(command) => command.api,
+            EntityType::Department(command) => command.api,
+            EntityType::Building(command) => command.api,
+            EntityType::Script(command) => command.api,
+            _ => Api::Classic,
+        }
+    }
+}
+
 #[derive(Debug, Args)]
 pub struct ComputerCommand {
+    /// API used to send the request; Jamf Pro API requests and responses are always JSON
+    #[arg(long, value_enum, default_value_t = Api::Classic, global = true)]
+    pub api: Api,
+
     #[clap(subcommand)]
     pub subcommand: ComputerSubcommand,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum ComputerSubcommand {
+    /// Create a computer record from an XML document
+    Create(Document),
     /// Delete an existing computer record
-    Delete(Id),
+    Delete(DeviceId),
+    /// Update an existing computer record from an XML document
+    Update(DeviceUpdate),
     /// Show an existing computer record
-    Show(Id),
+    Show(DeviceShow),
     /// Search for existing computer records by name, MAC address, username, etc; Accepts * wildcard
     /// Note: Wildcard searches must be wrapped in quotes or escaped to prevent shell expansion
     Search { search_query: String },
     /// List all computers
-    List,
+    List(ListOptions),
 }
 
 #[derive(Debug, Args)]
 pub struct MobileCommand {
+    /// API used to send the request; Jamf Pro API requests and responses are always JSON
+    #[arg(long, value_enum, default_value_t = Api::Classic, global = true)]
+    pub api: Api,
+
     #[clap(subcommand)]
     pub subcommand: MobileSubcommand,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum MobileSubcommand {
+    /// Create a mobile device record from an XML document
+    Create(Document),
     /// Delete an existing mobile device record
-    Delete(Id),
+    Delete(DeviceId),
+    /// Update an existing mobile device record from an XML document
+    Update(DeviceUpdate),
     /// Show an existing mobile device record
-    Show(Id),
+    Show(DeviceShow),
     /// Search for existing mobile device records by name, MAC address, username, etc. Accepts * wildcard
     //  Note: Wildcard searches must be wrapped in quotes or escaped to prevent shell expansion
     Search { search_query: String },
     /// List all mobile device records
-    List,
+    List(ListOptions),
 }
 
 #[derive(Debug, Args)]
@@ -124,8 +259,12 @@ pub struct UserCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum UserSubcommand {
+    /// Create a user record from an XML document
+    Create(Document),
     /// Delete an existing user record
     Delete(Id),
+    /// Update an existing user record from an XML document
+    Update(Update),
     /// Show an existing user record
     Show(Id),
     /// List all user records
@@ -140,8 +279,12 @@ pub struct PolicyCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum PolicySubcommand {
+    /// Create a policy from an XML document
+    Create(Document),
     /// Delete an existing policy record
     Delete(Id),
+    /// Update an existing policy from an XML document
+    Update(Update),
     /// Show an existing policy record
     Show(Id),
     /// List all policy records
@@ -150,50 +293,74 @@ pub enum PolicySubcommand {
 
 #[derive(Debug, Args)]
 pub struct PackageCommand {
+    /// API used to send the request; Jamf Pro API requests and responses are always JSON
+    #[arg(long, value_enum, default_value_t = Api::Classic, global = true)]
+    pub api: Api,
+
     #[clap(subcommand)]
     pub subcommand: PackageSubcommand,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum PackageSubcommand {
+    /// Create a package record from an XML document
+    Create(Document),
     /// Delete an existing package record
     Delete(Id),
+    /// Update an existing package record from an XML document
+    Update(Update),
     /// Show an existing package record
     Show(Id),
     /// List all package records
-    List,
+    List(ListOptions),
 }
 
 #[derive(Debug, Args)]
 pub struct CategoryCommand {
+    /// API used to send the request; Jamf Pro API requests and responses are always JSON
+    #[arg(long, value_enum, default_value_t = Api::Classic, global = true)]
+    pub api: Api,
+
     #[clap(subcommand)]
     pub subcommand: CategorySubcommand,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum CategorySubcommand {
+    /// Create a category from an XML document
+    Create(Document),
     /// Delete an existing category record
     Delete(Id),
+    /// Update an existing category from an XML document
+    Update(Update),
     /// Show an existing category record
     Show(Id),
     /// List all category records
-    List,
+    List(ListOptions),
 }
 
 #[derive(Debug, Args)]
 pub struct DepartmentCommand {
+    /// API used to send the request; Jamf Pro API requests and responses are always JSON
+    #[arg(long, value_enum, default_value_t = Api::Classic, global = true)]
+    pub api: Api,
+
     #[clap(subcommand)]
     pub subcommand: DepartmentSubcommand,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum DepartmentSubcommand {
+    /// Create a department from an XML document
+    Create(Document),
     /// Delete an existing department record
     Delete(Id),
+    /// Update an existing department from an XML document
+    Update(Update),
     /// Show an existing department record
     Show(Id),
     /// List all department records
-    List,
+    List(ListOptions),
 }
 
 #[derive(Debug, Args)]
@@ -204,8 +371,12 @@ pub struct EbookCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum EbookSubcommand {
+    /// Create an ebook from an XML document
+    Create(Document),
     /// Delete an existing ebook record
     Delete(Id),
+    /// Update an existing ebook from an XML document
+    Update(Update),
     /// Show an existing ebook record
     Show(Id),
     /// List all ebook records
@@ -214,18 +385,26 @@ pub enum EbookSubcommand {
 
 #[derive(Debug, Args)]
 pub struct BuildingCommand {
+    /// API used to send the request; Jamf Pro API requests and responses are always JSON
+    #[arg(long, value_enum, default_value_t = Api::Classic, global = true)]
+    pub api: Api,
+
     #[clap(subcommand)]
     pub subcommand: BuildingSubcommand,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum BuildingSubcommand {
+    /// Create a building from an XML document
+    Create(Document),
     /// Delete an existing buildings record
     Delete(Id),
+    /// Update an existing building from an XML document
+    Update(Update),
     /// Show an existing buildings record
     Show(Id),
     /// List all buildings records
-    List,
+    List(ListOptions),
 }
 
 #[derive(Debug, Args)]
@@ -236,8 +415,12 @@ pub struct MacAppCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum MacAppSubcommand {
+    /// Create a mac application from an XML document
+    Create(Document),
     /// Delete an existing mac application record
     Delete(Id),
+    /// Update an existing mac application from an XML document
+    Update(Update),
     /// Show an existing mac application record
     Show(Id),
     /// List all mac application records
@@ -252,8 +435,12 @@ pub struct MobileAppCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum MobileAppSubcommand {
+    /// Create a mobile device application from an XML document
+    Create(Document),
     /// Delete an existing mobile device application record
     Delete(Id),
+    /// Update an existing mobile device application from an XML document
+    Update(Update),
     /// Show an existing mobile device application record
     Show(Id),
     /// List all mobile device application records
@@ -262,18 +449,26 @@ pub enum MobileAppSubcommand {
 
 #[derive(Debug, Args)]
 pub struct ScriptCommand {
+    /// API used to send the request; Jamf Pro API requests and responses are always JSON
+    #[arg(long, value_enum, default_value_t = Api::Classic, global = true)]
+    pub api: Api,
+
     #[clap(subcommand)]
     pub subcommand: ScriptSubcommand,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum ScriptSubcommand {
+    /// Create a script from an XML document
+    Create(Document),
     /// Delete an existing script
     Delete(Id),
+    /// Update an existing script from an XML document
+    Update(Update),
     /// Show an existing script
     Show(Id),
     /// List all script
-    List,
+    List(ListOptions),
 }
 
 #[derive(Debug, Args)]
@@ -284,8 +479,12 @@ pub struct RestrictedSoftwareCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum RestrictedSoftwareSubcommand {
+    /// Create restricted software from an XML document
+    Create(Document),
     /// Delete an existing restricted software
     Delete(Id),
+    /// Update existing restricted software from an XML document
+    Update(Update),
     /// Show an existing restricted software
     Show(Id),
     /// List all restricted software
@@ -300,8 +499,12 @@ pub struct PrinterCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum PrinterSubcommand {
+    /// Create a printer from an XML document
+    Create(Document),
     /// Delete an existing printer
     Delete(Id),
+    /// Update an existing printer from an XML document
+    Update(Update),
     /// Show an existing printer
     Show(Id),
     /// List all printers
@@ -338,8 +541,18 @@ pub enum PatchSubcommand {
 
 #[derive(Debug, Subcommand)]
 pub enum PatchPolicyCommand {
+    /// Create a patch policy for a patch software title from an XML document
+    Create {
+        /// Id of the patch software title the policy is for
+        #[arg(long, value_parser = u32_to_string_validator)]
+        software_title_id: String,
+        #[command(flatten)]
+        document: Document,
+    },
     /// Delete an existing patch policy
     Delete(Id),
+    /// Update an existing patch policy from an XML document
+    Update(Update),
     /// Show an existing patch policy
     Show(Id),
     /// List all patch policies
@@ -360,8 +573,12 @@ pub enum PatchReportCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum PatchSoftwareTitleCommand {
+    /// Create a patch software title from an XML document
+    Create(Document),
     /// Delete an existing patch software title
     Delete(Id),
+    /// Update an existing patch software title from an XML document
+    Update(Update),
     /// Show an existing patch software title
     Show(Id),
     /// List all patch software titles
@@ -377,8 +594,12 @@ pub enum PatchAvailableTitleCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum PatchExternalSourceCommand {
+    /// Create an external patch source from an XML document
+    Create(Document),
     /// Delete an existing external patch source
     Delete(Id),
+    /// Update an existing external patch source from an XML document
+    Update(Update),
     /// Show an existing external patch source
     Show(Id),
     /// List all external patch sources
@@ -414,8 +635,12 @@ pub enum GroupSubcommand {
 
 #[derive(Debug, Subcommand)]
 pub enum ComputerGroupCommand {
+    /// Create a computer group from an XML document
+    Create(Document),
     /// Delete an existing computer group
     Delete(Id),
+    /// Update an existing computer group from an XML document
+    Update(Update),
     /// Show an existing computer group
     Show(Id),
     /// List all computer groups
@@ -424,8 +649,12 @@ pub enum ComputerGroupCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum MobileGroupCommand {
+    /// Create a mobile device group from an XML document
+    Create(Document),
     /// Delete an existing mobile device group
     Delete(Id),
+    /// Update an existing mobile device group from an XML document
+    Update(Update),
     /// Show an existing mobile device group
     Show(Id),
     /// List all mobile device groups
@@ -434,8 +663,12 @@ pub enum MobileGroupCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum UserGroupCommand {
+    /// Create a user group from an XML document
+    Create(Document),
     /// Delete an existing user group
     Delete(Id),
+    /// Update an existing user group from an XML document
+    Update(Update),
     /// Show an existing user group
     Show(Id),
     /// List all user groups
@@ -463,8 +696,12 @@ pub enum AdvSearchSubcommand {
 
 #[derive(Debug, Subcommand)]
 pub enum ComputerAdvSearchCommand {
+    /// Create a computer advanced search from an XML document
+    Create(Document),
     /// Delete an existing computer advanced search
     Delete(Id),
+    /// Update an existing computer advanced search from an XML document
+    Update(Update),
     /// Show an existing computer advanced search
     Show(Id),
     /// List all computer advanced searches
@@ -473,8 +710,12 @@ pub enum ComputerAdvSearchCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum MobileAdvSearchCommand {
+    /// Create a mobile device advanced search from an XML document
+    Create(Document),
     /// Delete an existing mobile device advanced search
     Delete(Id),
+    /// Update an existing mobile device advanced search from an XML document
+    Update(Update),
     /// Show an existing mobile device advanced search
     Show(Id),
     /// List all mobile device advanced searches
@@ -483,8 +724,12 @@ pub enum MobileAdvSearchCommand {
 
 #[derive(Debug, Subcommand)]
 pub enum UserAdvSearchCommand {
+    /// Create a user advanced search from an XML document
+    Create(Document),
     /// Delete an existing user advanced search
     Delete(Id),
+    /// Update an existing user advanced search from an XML document
+    Update(Update),
     /// Show an existing user advanced search
     Show(Id),
     /// List all user advanced searches
@@ -499,6 +744,87 @@ pub struct Id {
     /// Query a range of Ids with the format START,FINISH inclusive
     #[arg(required = true, short, long, value_delimiter = ',', group = "id_range", value_parser = range_validator)]
     pub range: Vec<u32>,
+
+    /// Query records by name rather than Id; Repeat to query several records, or pass @FILE to read one name per line
+    #[arg(required = true, short, long, group = "id_range")]
+    pub name: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct DeviceId {
+    #[command(flatten)]
+    pub id: Id,
+
+    /// Query devices by serial number; Accepts a comma separated list, or @FILE to read one per line
+    #[arg(required = true, long, value_delimiter = ',', group = "id_range")]
+    pub serial: Vec<String>,
+
+    /// Query devices by UDID; Accepts a comma separated list, or @FILE to read one per line
+    #[arg(required = true, long, value_delimiter = ',', group = "id_range")]
+    pub udid: Vec<String>,
+
+    /// Query devices by MAC address; Accepts a comma separated list, or @FILE to read one per line
+    #[arg(required = true, long, value_delimiter = ',', group = "id_range")]
+    pub mac: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct Document {
+    /// Path to the XML document describing the record (JSON for the Jamf Pro API); Reads from stdin if omitted or '-'
+    #[arg(short, long)]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ListOptions {
+    /// Number of records requested per page; Jamf Pro API only. Defaults to 100
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2000))]
+    pub page_size: Option<u32>,
+
+    /// Sort criteria in the format PROPERTY:asc or PROPERTY:desc, e.g. general.name:asc; Jamf Pro API only
+    #[arg(long, value_delimiter = ',')]
+    pub sort: Vec<String>,
+
+    /// Maximum number of records to return; Jamf Pro API only
+    #[arg(long)]
+    pub limit: Option<usize>,
+
+    /// Only return records matching FIELD<OP>VALUE, where OP is one of ==, !=, <, <=, >, >=, =in=
+    /// or =out=, e.g. general.name==Jane's Mac; Jamf Pro API only. Repeat to combine filters
+    #[arg(long)]
+    pub filter: Vec<Filter>,
+
+    /// Return records matching any of the given filters, rather than all of them
+    #[arg(long, requires = "filter")]
+    pub any: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct Update {
+    #[command(flatten)]
+    pub id: Id,
+
+    #[command(flatten)]
+    pub document: Document,
+}
+
+#[derive(Debug, Args)]
+pub struct DeviceShow {
+    #[command(flatten)]
+    pub id: DeviceId,
+
+    /// Only fetch the given sections of the record, e.g. General,Hardware; Classic API only
+    #[arg(long, value_delimiter = ',')]
+    pub subset: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct DeviceUpdate {
+    #[command(flatten)]
+    pub id: DeviceId,
+
+    #[command(flatten)]
+    pub document: Document,
 }
 
 fn range_validator(s: &str) -> Result<u32, String> {
@@ -530,6 +856,18 @@ fn u32_to_string_validator(s: &str) -> Result<String, String> {
 }
 
 impl Id {
+    /// Records to query, either by Id or by name
+    pub fn args(&self) -> Result<ApiArgs, String> {
+        if self.name.is_empty() {
+            Ok(ApiArgs::Ids(self.get_ids()?))
+        } else {
+            Ok(ApiArgs::Lookup(
+                Identifier::Name,
+                read_identifiers(&self.name)?,
+            ))
+        }
+    }
+
     pub fn get_ids(&self) -> Result<Vec<String>, String> {
         let ids = if !self.id.is_empty() {
             self.id
@@ -553,3 +891,76 @@ impl Id {
         }
     }
 }
+
+impl DeviceId {
+    /// Devices to query, by Id, name, serial number, UDID or MAC address
+    pub fn args(&self) -> Result<ApiArgs, String> {
+        let (identifier, values) = if !self.serial.is_empty() {
+            (Identifier::SerialNumber, &self.serial)
+        } else if !self.udid.is_empty() {
+            (Identifier::Udid, &self.udid)
+        } else if !self.mac.is_empty() {
+            (Identifier::MacAddress, &self.mac)
+        } else {
+            return self.id.args();
+        };
+
+        Ok(ApiArgs::Lookup(identifier, read_identifiers(values)?))
+    }
+}
+
+/// Expands any @FILE values into the identifiers listed in the file, one per line. Blank lines and
+/// lines starting with # are skipped
+fn read_identifiers(values: &[String]) -> Result<Vec<String>, String> {
+    let mut identifiers = Vec::new();
+
+    for value in values {
+        match value.strip_prefix('@') {
+            Some(path) => {
+                let content = fs::read_to_string(path)
+                    .map_err(|err| format!("Failed to read {path}: {err}"))?;
+
+                identifiers.extend(
+                    content
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                        .map(String::from),
+                );
+            }
+            None => identifiers.push(value.trim().to_string()),
+        }
+    }
+
+    if identifiers.is_empty() {
+        return Err("No identifiers were given".to_string());
+    }
+
+    Ok(identifiers)
+}
+
+impl Document {
+    pub fn read(&self) -> Result<String, String> {
+        let content = match &self.file {
+            Some(path) if path.as_os_str() != "-" => fs::read_to_string(path)
+                .map_err(|err| format!("Failed to read {}: {err}", path.display()))?,
+            _ => io::read_to_string(io::stdin())
+                .map_err(|err| format!("Failed to read document from stdin: {err}"))?,
+        };
+
+        if content.trim().is_empty() {
+            return Err("Document is empty".to_string());
+        }
+
+        Ok(content)
+    }
+}
+
+impl ListOptions {
+    pub fn is_set(&self) -> bool {
+        self.page_size.is_some()
+            || !self.sort.is_empty()
+            || self.limit.is_some()
+            || !self.filter.is_empty()
+    }
+}
@@ -0,0 +1,170 @@
+use crate::args::ExportArgs;
+use crate::print_plan;
+use crate::summary::{self, Summary, EXIT_AUTH_FAILURE, EXIT_CONFIG_ERROR, EXIT_TOTAL_FAILURE};
+use chrono::Utc;
+use jamfrs_lib::api_service::api_endpoints::{Args, CommandDetails, RecordType, RECORD_TYPES};
+use jamfrs_lib::api_service::export::file_name;
+use jamfrs_lib::api_service::{CommandResult, JamfApiService, JamfError};
+use reqwest::Method;
+use serde_json::{json, Map};
+use std::fs;
+use std::time::Instant;
+
+/// Writes every record of the kinds asked for to the export directory, followed by a manifest of
+/// how many of each were written, and returns the code to exit with
+pub async fn export(
+    jamf_api_service: &mut JamfApiService,
+    args: &ExportArgs,
+    json: bool,
+    dry_run: bool,
+) -> i32 {
+    let record_types: Vec<&'static RecordType> = if args.all {
+        RECORD_TYPES.iter().collect()
+    } else {
+        let mut record_types = Vec::new();
+        for entity in &args.entities {
+            match RecordType::find(entity) {
+                Some(record_type) => record_types.push(record_type),
+                None => {
+                    let entities: Vec<&str> = RECORD_TYPES.iter().map(RecordType::entity).collect();
+                    eprintln!(
+                        "Unknown kind of record '{entity}'; Expected one of {}",
+                        entities.join(", ")
+                    );
+                    return EXIT_CONFIG_ERROR;
+                }
+            }
+        }
+        record_types
+    };
+
+    // Records are only known once they've been listed, so only the lists can be planned
+    if dry_run {
+        return print_plan(
+            record_types
+                .iter()
+                .enumerate()
+                .map(|(index, record_type)| jamf_api_service.plan_list_records(index, record_type))
+                .collect(),
+        );
+    }
+
+    if let Err(err) = fs::create_dir_all(&args.dir) {
+        eprintln!("Couldn't create {}: {err}", args.dir.display());
+        return EXIT_CONFIG_ERROR;
+    }
+
+    let version = match jamf_api_service.jamf_pro_version().await {
+        Ok(version) => Some(version),
+        Err(err @ JamfError::Auth { .. }) => {
+            eprintln!("{err}");
+            return EXIT_AUTH_FAILURE;
+        }
+        Err(err) => {
+            eprintln!("Couldn't fetch the Jamf Pro version: {err}");
+            None
+        }
+    };
+
+    let extension = if json { "json" } else { "xml" };
+    let mut summary = Summary::default();
+    let mut counts = Map::new();
+
+    for record_type in record_types {
+        let entity = record_type.entity();
+        let start = Instant::now();
+
+        let listed = match jamf_api_service.list_records(record_type).await {
+            Ok(listed) => listed,
+            Err(err) => {
+                summary.add(&CommandResult {
+                    index: 0,
+                    arg: Some(entity.to_string()),
+                    method: Method::GET,
+                    url: format!(
+                        "{}{}",
+                        jamf_api_service.server_address(),
+                        record_type.list.usage().url
+                    ),
+                    status: err.status(),
+                    elapsed: start.elapsed(),
+                    body: Err(err),
+                });
+                continue;
+            }
+        };
+
+        counts.insert(entity.to_string(), json!(0));
+        if listed.is_empty() {
+            continue;
+        }
+
+        let dir = args.dir.join(entity);
+        if let Err(err) = fs::create_dir_all(&dir) {
+            eprintln!("Couldn't create {}: {err}", dir.display());
+            return EXIT_CONFIG_ERROR;
+        }
+
+        let commands = CommandDetails {
+            args: Args::Ids(listed.iter().map(|record| record.id.clone()).collect()),
+            endpoint: record_type.show.usage(),
+            body: None,
+            subset: Vec::new(),
+        };
+        if let Err(err) = jamf_api_service.set_commands(commands) {
+            eprintln!("{err}");
+            return summary::exit_code(&err);
+        }
+        let mut rx = match jamf_api_service.process_commands().await {
+            Ok(rx) => rx,
+            Err(err) => {
+                eprintln!("{err}");
+                return summary::exit_code(&err);
+            }
+        };
+
+        let mut exported = 0;
+        while let Some(mut result) = rx.recv().await {
+            if let Ok(body) = &result.body {
+                let path = dir.join(file_name(&listed[result.index], extension));
+
+                match fs::write(&path, body) {
+                    Ok(()) => exported += 1,
+                    Err(err) => {
+                        result.body = Err(JamfError::Config(format!(
+                            "Couldn't write {}: {err}",
+                            path.display()
+                        )))
+                    }
+                }
+            }
+
+            summary.add(&result);
+        }
+
+        counts.insert(entity.to_string(), json!(exported));
+    }
+
+    let manifest = json!({
+        "server": jamf_api_service.server_address(),
+        "version": version,
+        "exported_at": Utc::now().to_rfc3339(),
+        "format": extension,
+        "records": counts,
+    });
+    let manifest_path = args.dir.join("manifest.json");
+    let manifest_written = fs::write(
+        &manifest_path,
+        serde_json::to_string_pretty(&manifest).unwrap(),
+    );
+
+    summary.print();
+
+    if let Err(err) = manifest_written {
+        eprintln!("Couldn't write {}: {err}", manifest_path.display());
+        return EXIT_TOTAL_FAILURE;
+    }
+
+    eprintln!("Records were exported to {}", args.dir.display());
+    summary.exit_code()
+}
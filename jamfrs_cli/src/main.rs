@@ -1,40 +1,126 @@
 mod arg_mappings;
 mod args;
+mod export;
+mod output;
+mod restore;
+mod summary;
+mod tabular;
 
-use args::JamfrsArgs;
+use args::{EntityType, JamfrsArgs};
+use chrono::Utc;
 use clap::Parser;
-use jamfrs_lib::api_service::JamfApiService;
+use jamfrs_lib::api_service::retry::RetryPolicy;
+use jamfrs_lib::api_service::{AuthMode, CommandResult, JamfApiService, PlannedRequest};
+use output::{Output, OutputFormat};
 use reqwest::Method;
-use serde_json::Value;
+use std::collections::BTreeMap;
 use std::io;
-use std::io::stdout;
 use std::process::exit;
-use xmltree::{Element, EmitterConfig};
+use summary::{Summary, EXIT_CONFIG_ERROR, EXIT_TOTAL_FAILURE};
 
 #[tokio::main]
 async fn main() {
     let args = JamfrsArgs::parse();
+    let auth = match (args.client_id, args.client_secret) {
+        (Some(client_id), Some(client_secret)) => AuthMode::ClientCredentials {
+            client_id,
+            client_secret,
+        },
+        // clap guarantees a username and password are present when no client credentials are given
+        _ => AuthMode::Basic {
+            username: args.username.unwrap(),
+            password: args.password.unwrap(),
+        },
+    };
+
+    // JSON output formats need the records themselves to be JSON
+    let request_json = args.json || args.output.is_some_and(|output| output.is_json());
+
+    if !args.columns.is_empty() && !args.output.is_some_and(|output| output.is_tabular()) {
+        eprintln!("--columns is only supported with --output csv or table");
+        exit(EXIT_CONFIG_ERROR);
+    }
+
+    if args.select.is_some() && args.output.is_some() {
+        eprintln!("--select can't be used with --output");
+        exit(EXIT_CONFIG_ERROR);
+    }
+
     let mut jamf_api_service = match JamfApiService::new(
         args.server_address,
         args.port,
-        args.username,
-        args.password,
+        auth,
         args.insecure,
-        args.json,
+        request_json,
     ) {
         Ok(service) => service,
         Err(err) => {
             eprintln!("Failed to create network service: {err}");
-            exit(1);
+            exit(summary::exit_code(&err));
+        }
+    };
+
+    jamf_api_service.set_max_concurrency(args.concurrency as usize);
+    jamf_api_service.set_retry_policy(RetryPolicy {
+        max_attempts: args.retries + 1,
+        retry_deletes: args.retry_deletes,
+        ..RetryPolicy::default()
+    });
+
+    if let EntityType::Export(export_args) = &args.entity_type {
+        exit(
+            export::export(
+                &mut jamf_api_service,
+                export_args,
+                request_json,
+                args.dry_run,
+            )
+            .await,
+        );
+    }
+
+    if let EntityType::Restore(restore_args) = &args.entity_type {
+        exit(restore::restore(&jamf_api_service, restore_args, args.dry_run).await);
+    }
+
+    let command_details = match arg_mappings::get_command_details(&args.entity_type) {
+        Ok(command_details) => command_details,
+        Err(err) => {
+            eprintln!("{err}");
+            exit(EXIT_CONFIG_ERROR);
         }
     };
 
-    match jamf_api_service
-        .set_commands(arg_mappings::get_command_details(&args.entity_type).unwrap())
-    {
+    // The Jamf Pro API always responds with JSON
+    let json = request_json || command_details.endpoint.is_jamf_pro_api();
+
+    if json && args.output == Some(OutputFormat::Xml) {
+        eprintln!("--output xml can't be used with --json or the Jamf Pro API");
+        exit(EXIT_CONFIG_ERROR);
+    }
+
+    // Deleted records are backed up unless asked not to, so they can be restored if deleted by mistake
+    let backup_dir =
+        (command_details.endpoint.method == Method::DELETE && !args.no_backup).then(|| {
+            args.backup_dir
+                .join(Utc::now().format("%Y%m%d-%H%M%S").to_string())
+        });
+    jamf_api_service.set_backup_dir(backup_dir.clone());
+
+    match jamf_api_service.set_commands(command_details) {
         Ok(command_details) => {
+            if args.dry_run {
+                exit(print_plan(jamf_api_service.plan()));
+            }
+
             if !args.confirm && command_details.endpoint.method == Method::DELETE {
                 let mut input = String::new();
+                if let Some(backup_dir) = &backup_dir {
+                    println!(
+                        "Records will be backed up to {} first",
+                        backup_dir.display()
+                    );
+                }
                 println!(
                     "Confirm you wish to DELETE {} record(s): (Y/N): ",
                     &jamf_api_service.number_of_commands()
@@ -49,54 +135,93 @@ async fn main() {
         }
         Err(err) => {
             eprintln!("{err}");
-            exit(1);
+            exit(summary::exit_code(&err));
         }
     }
 
-    let mut rx = jamf_api_service.process_commands().await;
-    let mut errors: Vec<String> = Vec::new();
-
-    loop {
-        let res = &rx.recv().await;
-
-        match res {
-            Some(res) => match res {
-                Ok(res) => {
-                    if !args.json && args.pretty {
-                        let parsed_xml = Element::parse(res.as_bytes()).unwrap();
-                        let mut emitter_config = EmitterConfig::new();
-                        emitter_config.perform_indent = true;
-
-                        parsed_xml
-                            .write_with_config(stdout(), emitter_config)
-                            .unwrap();
-                    } else if args.json && args.pretty {
-                        let json_obj: Result<Value, _> = serde_json::from_str(res);
-
-                        match json_obj {
-                            Ok(json) => {
-                                println!("{}", serde_json::to_string_pretty(&json).unwrap())
-                            }
-                            Err(_) => {
-                                // Gracefully handle the stupid scenario where jamf ignores our request for JSON and returns XML anyways
-                                // TODO: Convert XML into JSON when our request isn't respected
-                                println!("{res}")
-                            }
-                        }
-                    } else {
-                        print!("{res},");
-                    }
-                }
-                Err(err) => errors.push(err.to_string()),
-            },
-            None => {
-                // Channel has been closed and we're done
-                for err in &errors {
-                    println!("\nError: {err}");
-                }
+    let mut rx = match jamf_api_service.process_commands().await {
+        Ok(rx) => rx,
+        Err(err) => {
+            eprintln!("{err}");
+            exit(summary::exit_code(&err));
+        }
+    };
+
+    let mut output = Output::new(args.output, json, args.pretty, args.columns, args.select);
+    let mut summary = Summary::default();
+    let mut pending: BTreeMap<usize, CommandResult> = BTreeMap::new();
+    let mut next_index = 0;
+
+    output.begin().unwrap_or_else(output_failed);
+
+    while let Some(result) = rx.recv().await {
+        if !args.ordered {
+            print_result(result, &mut output, &mut summary);
+            continue;
+        }
+
+        // Hold results back until every result before them has been printed
+        pending.insert(result.index, result);
+        while let Some(result) = pending.remove(&next_index) {
+            print_result(result, &mut output, &mut summary);
+            next_index += 1;
+        }
+    }
+
+    // Channel has been closed and we're done
+    for result in pending.into_values() {
+        print_result(result, &mut output, &mut summary);
+    }
+
+    output.end().unwrap_or_else(output_failed);
+    summary.print();
+
+    if let Some(backup_dir) = backup_dir.filter(|dir| dir.exists()) {
+        eprintln!("Records were backed up to {}", backup_dir.display());
+    }
 
-                break;
+    exit(summary.exit_code());
+}
+
+fn print_result(result: CommandResult, output: &mut Output, summary: &mut Summary) {
+    summary.add(&result);
+
+    if let Ok(res) = &result.body {
+        output.record(res).unwrap_or_else(output_failed);
+    }
+}
+
+fn output_failed(err: io::Error) {
+    eprintln!("Failed to write output: {err}");
+    exit(EXIT_TOTAL_FAILURE)
+}
+
+/// Prints every request that would be sent, returning the code to exit with
+fn print_plan(plan: Vec<PlannedRequest>) -> i32 {
+    let mut exit_code = 0;
+
+    for request in plan {
+        let url = match request.url {
+            Ok(url) => url,
+            Err(err) => {
+                match request.arg {
+                    Some(arg) => eprintln!("Error for {arg}: {err}"),
+                    None => eprintln!("Error: {err}"),
+                }
+                exit_code = EXIT_CONFIG_ERROR;
+                continue;
             }
+        };
+
+        println!("{} {url}", request.method);
+        for (name, value) in &request.headers {
+            println!("{name}: {value}");
+        }
+        if let Some(body) = &request.body {
+            println!("\n{}", body.content.trim_end());
         }
+        println!();
     }
+
+    exit_code
 }
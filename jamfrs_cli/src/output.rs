@@ -0,0 +1,350 @@
+use crate::tabular::Table;
+use clap::ValueEnum;
+use jamfrs_lib::api_service::select::Selector;
+use jamfrs_lib::api_service::xml_json::xml_to_json;
+use serde_json::Value;
+use std::io::{self, stdout, Stdout, Write};
+use xmltree::{Element, EmitterConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// A single JSON array holding every record
+    Json,
+    /// One JSON record per line
+    Ndjson,
+    /// Every record wrapped in a <results> root element
+    Xml,
+    /// Records flattened into comma separated columns with a header row
+    Csv,
+    /// Records flattened into aligned columns
+    Table,
+}
+
+impl OutputFormat {
+    /// Returns true if records need to be requested as JSON for this format
+    pub fn is_json(&self) -> bool {
+        !matches!(self, OutputFormat::Xml)
+    }
+
+    /// Returns true if records are flattened into columns for this format
+    pub fn is_tabular(&self) -> bool {
+        matches!(self, OutputFormat::Csv | OutputFormat::Table)
+    }
+}
+
+/// Writes records to stdout, or another writer, as they arrive in the selected format
+pub struct Output<W: Write = Stdout> {
+    out: W,
+    format: Option<OutputFormat>,
+    json: bool,
+    pretty: bool,
+    records: usize,
+    table: Table,
+    select: Option<Selector>,
+}
+
+impl Output {
+    pub fn new(
+        format: Option<OutputFormat>,
+        json: bool,
+        pretty: bool,
+        columns: Vec<String>,
+        select: Option<Selector>,
+    ) -> Self {
+        Output::with_writer(stdout(), format, json, pretty, columns, select)
+    }
+}
+
+impl<W: Write> Output<W> {
+    pub fn with_writer(
+        out: W,
+        format: Option<OutputFormat>,
+        json: bool,
+        pretty: bool,
+        columns: Vec<String>,
+        select: Option<Selector>,
+    ) -> Self {
+        Output {
+            out,
+            format,
+            json,
+            pretty,
+            records: 0,
+            table: Table::new(columns),
+            select,
+        }
+    }
+
+    pub fn begin(&mut self) -> io::Result<()> {
+        match self.format {
+            Some(OutputFormat::Json) => write!(self.out, "["),
+            Some(OutputFormat::Xml) => write!(
+                self.out,
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?><results>"
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn record(&mut self, res: &str) -> io::Result<()> {
+        if let Some(select) = &self.select {
+            for value in select.select(&parse_json(res)) {
+                // Strings are printed without quotes so they can be used as they are in scripts
+                match value {
+                    Value::String(text) => writeln!(self.out, "{text}")?,
+                    _ if self.pretty => {
+                        writeln!(self.out, "{}", serde_json::to_string_pretty(value)?)?
+                    }
+                    _ => writeln!(self.out, "{value}")?,
+                }
+            }
+            self.records += 1;
+            return Ok(());
+        }
+
+        match self.format {
+            Some(OutputFormat::Json) => {
+                let separator = if self.records == 0 { "" } else { "," };
+                if self.pretty {
+                    write!(
+                        self.out,
+                        "{separator}\n{}",
+                        indent(&serde_json::to_string_pretty(&parse_json(res))?)
+                    )?;
+                } else {
+                    write!(self.out, "{separator}{}", parse_json(res))?;
+                }
+            }
+            Some(OutputFormat::Ndjson) => writeln!(self.out, "{}", parse_json(res))?,
+            Some(OutputFormat::Xml) => match Element::parse(res.as_bytes()) {
+                Ok(element) => {
+                    let mut emitter_config = EmitterConfig::new().write_document_declaration(false);
+                    emitter_config.perform_indent = self.pretty;
+                    if self.pretty {
+                        writeln!(self.out)?;
+                    }
+
+                    element
+                        .write_with_config(&mut self.out, emitter_config)
+                        .map_err(io::Error::other)?;
+                }
+                // Not XML, so write it as text rather than produce a broken document
+                Err(_) => write!(self.out, "<result>{}</result>", escape_xml(res))?,
+            },
+            // Columns are only known once every record has arrived
+            Some(OutputFormat::Csv | OutputFormat::Table) => self.table.add(parse_json(res)),
+            None => self.legacy_record(res)?,
+        }
+
+        self.records += 1;
+        self.out.flush()
+    }
+
+    pub fn end(&mut self) -> io::Result<()> {
+        match self.format {
+            Some(OutputFormat::Json) if self.pretty && self.records > 0 => {
+                writeln!(self.out, "\n]")
+            }
+            Some(OutputFormat::Json) => writeln!(self.out, "]"),
+            Some(OutputFormat::Xml) if self.pretty => writeln!(self.out, "\n</results>"),
+            Some(OutputFormat::Xml) => writeln!(self.out, "</results>"),
+            Some(OutputFormat::Csv) => self.table.write_csv(&mut self.out),
+            Some(OutputFormat::Table) => self.table.write_table(&mut self.out),
+            Some(OutputFormat::Ndjson) | None => Ok(()),
+        }
+    }
+
+    fn legacy_record(&mut self, res: &str) -> io::Result<()> {
+        if !self.json && self.pretty {
+            // Error pages and Jamf Pro API responses aren't XML, so they're written as they are
+            let Ok(parsed_xml) = Element::parse(res.as_bytes()) else {
+                return writeln!(self.out, "{res}");
+            };
+            let mut emitter_config = EmitterConfig::new();
+            emitter_config.perform_indent = true;
+
+            parsed_xml
+                .write_with_config(&mut self.out, emitter_config)
+                .map_err(io::Error::other)
+        } else if self.json && self.pretty {
+            writeln!(
+                self.out,
+                "{}",
+                serde_json::to_string_pretty(&parse_json(res))?
+            )
+        } else if self.json {
+            write!(self.out, "{},", parse_json(res))
+        } else {
+            write!(self.out, "{res},")
+        }
+    }
+}
+
+// Jamf ignores requests for JSON when deleting records and for some patch endpoints, so XML is
+// converted, and anything else is kept as a string
+fn parse_json(res: &str) -> Value {
+    serde_json::from_str(res)
+        .or_else(|_| xml_to_json(res))
+        .unwrap_or_else(|_| Value::String(res.to_string()))
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("  {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_json, Output, OutputFormat};
+    use jamfrs_lib::api_service::select::Selector;
+    use serde_json::json;
+
+    const RECORDS: [&str; 2] = [
+        r#"{"computer":{"id":1,"name":"one"}}"#,
+        r#"{"computer":{"id":2,"name":"two, \"quoted\""}}"#,
+    ];
+
+    fn render(
+        format: Option<OutputFormat>,
+        json: bool,
+        select: Option<&str>,
+        records: &[&str],
+    ) -> String {
+        let mut out = Vec::new();
+        let mut output = Output::with_writer(
+            &mut out,
+            format,
+            json,
+            false,
+            Vec::new(),
+            select.map(|path| path.parse::<Selector>().unwrap()),
+        );
+
+        output.begin().unwrap();
+        for record in records {
+            output.record(record).unwrap();
+        }
+        output.end().unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_parse_json() {
+        assert_eq!(
+            parse_json(r#"{"computer":{"id":1}}"#),
+            json!({"computer": {"id": 1}})
+        );
+        // Jamf responds to deletes with XML even when asked for JSON
+        assert_eq!(
+            parse_json("<?xml version=\"1.0\" encoding=\"UTF-8\"?><computer><id>1</id></computer>"),
+            json!({"computer": {"id": "1"}})
+        );
+        assert_eq!(parse_json("Not Found"), json!("Not Found"));
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            render(Some(OutputFormat::Json), true, None, &RECORDS),
+            format!("[{},{}]\n", RECORDS[0], RECORDS[1])
+        );
+        assert_eq!(render(Some(OutputFormat::Json), true, None, &[]), "[]\n");
+
+        // XML responses are converted rather than breaking the array
+        assert_eq!(
+            render(
+                Some(OutputFormat::Json),
+                true,
+                None,
+                &["<computer><id>3</id></computer>"]
+            ),
+            "[{\"computer\":{\"id\":\"3\"}}]\n"
+        );
+    }
+
+    #[test]
+    fn test_ndjson() {
+        assert_eq!(
+            render(Some(OutputFormat::Ndjson), true, None, &RECORDS),
+            format!("{}\n{}\n", RECORDS[0], RECORDS[1])
+        );
+    }
+
+    #[test]
+    fn test_xml() {
+        let records = [
+            "<computer><id>1</id></computer>",
+            "<computer><id>2</id></computer>",
+        ];
+
+        assert_eq!(
+            render(Some(OutputFormat::Xml), false, None, &records),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><results><computer><id>1</id></computer>\
+            <computer><id>2</id></computer></results>\n"
+        );
+        assert_eq!(
+            render(Some(OutputFormat::Xml), false, None, &["Not <XML>"]),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><results><result>Not &lt;XML&gt;</result>\
+            </results>\n"
+        );
+    }
+
+    #[test]
+    fn test_tabular() {
+        assert_eq!(
+            render(Some(OutputFormat::Csv), true, None, &RECORDS),
+            "id,name\n1,one\n2,\"two, \"\"quoted\"\"\"\n"
+        );
+        assert_eq!(
+            render(Some(OutputFormat::Table), true, None, &RECORDS),
+            "id  name\n--  -------------\n1   one\n2   two, \"quoted\"\n"
+        );
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(render(None, true, Some("general.name"), &RECORDS), "");
+        assert_eq!(
+            render(None, true, Some("name"), &RECORDS),
+            "one\ntwo, \"quoted\"\n"
+        );
+        assert_eq!(
+            render(None, true, Some("computer"), &RECORDS[..1]),
+            "{\"id\":1,\"name\":\"one\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_legacy() {
+        assert_eq!(
+            render(None, true, None, &RECORDS),
+            format!("{},{},", RECORDS[0], RECORDS[1])
+        );
+        assert_eq!(
+            render(None, false, None, &["<computer><id>1</id></computer>"]),
+            "<computer><id>1</id></computer>,"
+        );
+    }
+
+    #[test]
+    fn test_legacy_pretty_not_xml() {
+        let mut out = Vec::new();
+        let mut output = Output::with_writer(&mut out, None, false, true, Vec::new(), None);
+        output.record("<html>Bad Gateway").unwrap();
+        output.record(r#"{"version":"11.0.0"}"#).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "<html>Bad Gateway\n{\"version\":\"11.0.0\"}\n"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::args::RestoreArgs;
+use crate::print_plan;
+use crate::summary::{self, Summary, EXIT_CONFIG_ERROR};
+use jamfrs_lib::api_service::restore::{prepare, read_saved_records, IdMap};
+use jamfrs_lib::api_service::{CommandResult, JamfApiService, JamfError};
+use reqwest::{Method, StatusCode};
+use std::time::Instant;
+
+/// Creates every saved record found at the restore path, printing the id each was given, and
+/// returns the code to exit with
+pub async fn restore(jamf_api_service: &JamfApiService, args: &RestoreArgs, dry_run: bool) -> i32 {
+    let records = match read_saved_records(&args.path) {
+        Ok(records) if records.is_empty() => {
+            eprintln!("No records found in {}", args.path.display());
+            return EXIT_CONFIG_ERROR;
+        }
+        Ok(records) => records,
+        Err(err) => {
+            eprintln!("{err}");
+            return summary::exit_code(&err);
+        }
+    };
+
+    let mut id_map = IdMap::default();
+    let mut summary = Summary::default();
+    let mut plan = Vec::new();
+
+    // Records are created one at a time, in order, so the ids of records referenced by later ones
+    // are known by the time those are prepared
+    for (index, record) in records.iter().enumerate() {
+        let arg = record.path.display().to_string();
+        let document = prepare(&record.document, args.rewrite_references.then_some(&id_map));
+
+        if dry_run {
+            match document {
+                Ok(document) => plan.push(jamf_api_service.plan_create_record(
+                    index,
+                    record.record_type,
+                    document,
+                )),
+                Err(err) => {
+                    eprintln!("Error for {arg}: {err}");
+                    return EXIT_CONFIG_ERROR;
+                }
+            }
+            continue;
+        }
+
+        let start = Instant::now();
+        let created = match document {
+            Ok(document) => {
+                jamf_api_service
+                    .create_record(record.record_type, document)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        let entity = record.record_type.entity();
+        let old_id = record.id.as_deref().unwrap_or("(no id)");
+        match &created {
+            Ok(Some(new_id)) => {
+                println!("{entity} {old_id} -> {new_id}");
+
+                if let Some(old_id) = &record.id {
+                    id_map.insert(record.record_type, old_id, new_id);
+                }
+            }
+            Ok(None) => {
+                println!("{entity} {old_id} -> (unknown)");
+                eprintln!(
+                    "Warning: {arg} was created, but the response didn't include its new id; References to it won't be rewritten"
+                );
+            }
+            Err(_) => {}
+        }
+
+        // Every record after this one would be rejected the same way
+        let auth_failed = matches!(created, Err(JamfError::Auth { .. }));
+
+        summary.add(&CommandResult {
+            index,
+            arg: Some(arg),
+            method: Method::POST,
+            url: jamf_api_service
+                .create_url(record.record_type)
+                .unwrap_or_default(),
+            status: match &created {
+                Ok(_) => Some(StatusCode::CREATED),
+                Err(err) => err.status(),
+            },
+            elapsed: start.elapsed(),
+            body: created.map(|new_id| new_id.unwrap_or_default()),
+        });
+
+        if auth_failed {
+            break;
+        }
+    }
+
+    if dry_run {
+        return print_plan(plan);
+    }
+
+    summary.print();
+    summary.exit_code()
+}
@@ -0,0 +1,197 @@
+use jamfrs_lib::api_service::{CommandResult, JamfError};
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+
+/// Every request failed, or the server couldn't be reached
+pub const EXIT_TOTAL_FAILURE: i32 = 1;
+/// Invalid arguments, server address or documents; Matches the code clap exits with for usage errors
+pub const EXIT_CONFIG_ERROR: i32 = 2;
+/// Credentials were rejected or a bearer token couldn't be obtained
+pub const EXIT_AUTH_FAILURE: i32 = 3;
+/// Some requests failed while others succeeded
+pub const EXIT_PARTIAL_FAILURE: i32 = 4;
+
+/// Returns the exit code for an error that stopped the run before any request was sent
+pub fn exit_code(err: &JamfError) -> i32 {
+    match err {
+        JamfError::Auth { .. } => EXIT_AUTH_FAILURE,
+        JamfError::Config(_) => EXIT_CONFIG_ERROR,
+        _ => EXIT_TOTAL_FAILURE,
+    }
+}
+
+/// Tally of the results of a run, reported to stderr once every result has arrived
+#[derive(Default)]
+pub struct Summary {
+    succeeded: usize,
+    failed: usize,
+    auth_failures: usize,
+    /// Number of results per status code; None for requests that received no response
+    statuses: BTreeMap<Option<StatusCode>, usize>,
+    errors: Vec<String>,
+}
+
+impl Summary {
+    pub fn add(&mut self, result: &CommandResult) {
+        *self.statuses.entry(result.status).or_default() += 1;
+
+        let Err(err) = &result.body else {
+            self.succeeded += 1;
+            return;
+        };
+
+        self.failed += 1;
+        if matches!(err, JamfError::Auth { .. }) || result.status == Some(StatusCode::UNAUTHORIZED)
+        {
+            self.auth_failures += 1;
+        }
+
+        match &result.arg {
+            Some(arg) => self.errors.push(format!("Error for {arg}: {err}")),
+            None => self.errors.push(format!("Error: {err}")),
+        }
+    }
+
+    /// Writes every error followed by the number of results per status code to stderr
+    pub fn print(&self) {
+        for err in &self.errors {
+            eprintln!("\n{err}");
+        }
+
+        eprintln!("\n{} succeeded, {} failed", self.succeeded, self.failed);
+
+        for (status, count) in &self.statuses {
+            match status {
+                Some(status) => eprintln!("  {status}: {count}"),
+                None => eprintln!("  No response: {count}"),
+            }
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match (self.succeeded, self.failed) {
+            (_, 0) => 0,
+            (0, failed) if failed == self.auth_failures => EXIT_AUTH_FAILURE,
+            (0, _) => EXIT_TOTAL_FAILURE,
+            _ => EXIT_PARTIAL_FAILURE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        exit_code, Summary, EXIT_AUTH_FAILURE, EXIT_CONFIG_ERROR, EXIT_PARTIAL_FAILURE,
+        EXIT_TOTAL_FAILURE,
+    };
+    use jamfrs_lib::api_service::{CommandResult, JamfError};
+    use reqwest::{Method, StatusCode};
+    use std::time::Duration;
+
+    fn result(body: Result<String, JamfError>) -> CommandResult {
+        CommandResult {
+            index: 0,
+            arg: Some(String::from("1")),
+            method: Method::GET,
+            url: String::from("https://test.jamfcloud.com:443/JSSResource/computers/id/1"),
+            status: match &body {
+                Ok(_) => Some(StatusCode::OK),
+                Err(err) => err.status(),
+            },
+            elapsed: Duration::ZERO,
+            body,
+        }
+    }
+
+    fn not_found() -> JamfError {
+        JamfError::Http {
+            status: StatusCode::NOT_FOUND,
+            url: String::from("/JSSResource/computers/id/1"),
+        }
+    }
+
+    fn token_rejected() -> JamfError {
+        JamfError::Auth {
+            status: StatusCode::UNAUTHORIZED,
+            reason: String::from("Token rejected for /JSSResource/computers/id/1"),
+        }
+    }
+
+    fn summarize(results: Vec<Result<String, JamfError>>) -> Summary {
+        let mut summary = Summary::default();
+        for body in results {
+            summary.add(&result(body));
+        }
+        summary
+    }
+
+    #[test]
+    fn test_all_succeeded() {
+        let summary = summarize(vec![Ok(String::new()), Ok(String::new())]);
+
+        assert_eq!(summary.succeeded, 2);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(summary.exit_code(), 0);
+        assert_eq!(Summary::default().exit_code(), 0);
+    }
+
+    #[test]
+    fn test_total_failure() {
+        let summary = summarize(vec![
+            Err(not_found()),
+            Err(JamfError::Transport(String::from("timed out"))),
+        ]);
+
+        assert_eq!(summary.failed, 2);
+        assert_eq!(summary.statuses.get(&Some(StatusCode::NOT_FOUND)), Some(&1));
+        assert_eq!(summary.statuses.get(&None), Some(&1));
+        assert_eq!(summary.exit_code(), EXIT_TOTAL_FAILURE);
+    }
+
+    #[test]
+    fn test_config_error() {
+        assert_eq!(
+            exit_code(&JamfError::Config(String::from("Invalid port"))),
+            EXIT_CONFIG_ERROR
+        );
+        assert_eq!(exit_code(&token_rejected()), EXIT_AUTH_FAILURE);
+        assert_eq!(exit_code(&not_found()), EXIT_TOTAL_FAILURE);
+    }
+
+    #[test]
+    fn test_auth_failure() {
+        let summary = summarize(vec![Err(token_rejected()), Err(token_rejected())]);
+
+        // Rejected tokens are grouped under their status rather than as requests without a response
+        assert_eq!(
+            summary.statuses.get(&Some(StatusCode::UNAUTHORIZED)),
+            Some(&2)
+        );
+        assert_eq!(summary.statuses.get(&None), None);
+        assert_eq!(summary.exit_code(), EXIT_AUTH_FAILURE);
+
+        // Other failures alongside the rejected tokens aren't down to the credentials alone
+        let summary = summarize(vec![Err(token_rejected()), Err(not_found())]);
+        assert_eq!(summary.exit_code(), EXIT_TOTAL_FAILURE);
+    }
+
+    #[test]
+    fn test_partial_failure() {
+        let summary = summarize(vec![
+            Ok(String::new()),
+            Err(not_found()),
+            Err(token_rejected()),
+        ]);
+
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.failed, 2);
+        assert_eq!(
+            summary.errors,
+            vec![
+                format!("Error for 1: {}", not_found()),
+                format!("Error for 1: {}", token_rejected())
+            ]
+        );
+        assert_eq!(summary.exit_code(), EXIT_PARTIAL_FAILURE);
+    }
+}
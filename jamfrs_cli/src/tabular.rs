@@ -0,0 +1,263 @@
+use serde_json::{json, Value};
+use std::io::{self, Write};
+
+/// Records flattened into rows, keyed by dotted field names such as general.serial_number, to be
+/// written as CSV or an aligned table once every record has arrived
+pub struct Table {
+    /// Columns to write; Every field seen, in order of appearance, when empty
+    columns: Vec<String>,
+    rows: Vec<Vec<(String, String)>>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<String>) -> Self {
+        Table {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Adds the records of a response. Lists and search results such as {"computers": [...]}
+    /// add a row per record, while single records such as {"computer": {...}} add one row
+    pub fn add(&mut self, response: Value) {
+        for record in records(response) {
+            let mut row = Vec::new();
+            flatten("", record, &mut row);
+            self.rows.push(row);
+        }
+    }
+
+    pub fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        let columns = self.columns();
+        writeln!(out, "{}", csv_line(columns.iter().map(String::as_str)))?;
+
+        for row in &self.rows {
+            writeln!(
+                out,
+                "{}",
+                csv_line(columns.iter().map(|column| cell(row, column)))
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_table(&self, out: &mut impl Write) -> io::Result<()> {
+        let columns = self.columns();
+        let cells: Vec<Vec<&str>> = self
+            .rows
+            .iter()
+            .map(|row| columns.iter().map(|column| cell(row, column)).collect())
+            .collect();
+
+        let widths: Vec<usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([column.chars().count()])
+                    .max()
+                    .unwrap()
+            })
+            .collect();
+
+        let header: Vec<&str> = columns.iter().map(String::as_str).collect();
+        let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        let rule: Vec<&str> = rule.iter().map(String::as_str).collect();
+
+        for line in [header, rule].iter().chain(&cells) {
+            let padded: Vec<String> = line
+                .iter()
+                .zip(&widths)
+                .map(|(text, width)| format!("{text:<width$}"))
+                .collect();
+            writeln!(out, "{}", padded.join("  ").trim_end())?;
+        }
+
+        Ok(())
+    }
+
+    fn columns(&self) -> Vec<String> {
+        if !self.columns.is_empty() {
+            return self.columns.clone();
+        }
+
+        let mut columns: Vec<String> = Vec::new();
+        for (field, _) in self.rows.iter().flatten() {
+            if !columns.contains(field) {
+                columns.push(field.clone());
+            }
+        }
+
+        columns
+    }
+}
+
+/// Unwraps the root of a response, e.g. {"computers": [...]} or {"computer": {...}}, into its
+/// records. Jamf Pro API records aren't wrapped so are returned as they are
+fn records(response: Value) -> Vec<Value> {
+    match response {
+        Value::Array(records) => records,
+        Value::Object(root) if root.len() == 1 => {
+            let (key, inner) = root.into_iter().next().unwrap();
+            match inner {
+                Value::Array(records) => records,
+                Value::Object(_) => vec![inner],
+                _ => vec![json!({ key: inner })],
+            }
+        }
+        _ => vec![response],
+    }
+}
+
+fn flatten(prefix: &str, value: Value, row: &mut Vec<(String, String)>) {
+    let field = if prefix.is_empty() { "value" } else { prefix };
+
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                let key = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&key, value, row);
+            }
+        }
+        Value::Array(values) if values.is_empty() => row.push((field.to_string(), String::new())),
+        // Arrays nested in a record, such as a policy's scope, stay as JSON in a single cell
+        Value::Array(_) => row.push((field.to_string(), value.to_string())),
+        Value::String(text) => row.push((field.to_string(), text)),
+        Value::Null => row.push((field.to_string(), String::new())),
+        _ => row.push((field.to_string(), value.to_string())),
+    }
+}
+
+/// Finds the value of a column, matching either the full dotted field name or its last part, so
+/// serial_number finds general.serial_number
+fn cell<'a>(row: &'a [(String, String)], column: &str) -> &'a str {
+    let suffix = format!(".{column}");
+
+    row.iter()
+        .find(|(field, _)| field == column)
+        .or_else(|| row.iter().find(|(field, _)| field.ends_with(&suffix)))
+        .map(|(_, value)| value.as_str())
+        .unwrap_or("")
+}
+
+fn csv_line<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    fields
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cell, csv_line, flatten, Table};
+    use serde_json::{json, Value};
+
+    fn flattened(value: Value) -> Vec<(String, String)> {
+        let mut row = Vec::new();
+        flatten("", value, &mut row);
+        row
+    }
+
+    fn row(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+        fields
+            .iter()
+            .map(|(field, value)| (field.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_flatten() {
+        assert_eq!(
+            flattened(json!({
+                "id": 12,
+                "general": {"name": "Jane's Mac", "remote_management": {"managed": true}},
+                "groups": [{"id": 1}],
+                "printers": [],
+                "asset_tag": null
+            })),
+            row(&[
+                ("id", "12"),
+                ("general.name", "Jane's Mac"),
+                ("general.remote_management.managed", "true"),
+                ("groups", "[{\"id\":1}]"),
+                ("printers", ""),
+                ("asset_tag", ""),
+            ])
+        );
+        assert_eq!(
+            flattened(json!("C02XL0GZJGH5")),
+            row(&[("value", "C02XL0GZJGH5")])
+        );
+    }
+
+    #[test]
+    fn test_cell() {
+        let fields = row(&[
+            ("id", "12"),
+            ("general.id", "13"),
+            ("general.serial_number", "C02XL0GZJGH5"),
+            ("hardware.serial_number", "C02YJ1ABJGH6"),
+        ]);
+
+        // Exact names win over suffixes, and the first field with the suffix is used otherwise
+        assert_eq!(cell(&fields, "id"), "12");
+        assert_eq!(cell(&fields, "general.id"), "13");
+        assert_eq!(cell(&fields, "serial_number"), "C02XL0GZJGH5");
+        assert_eq!(cell(&fields, "hardware.serial_number"), "C02YJ1ABJGH6");
+        // Suffixes only match whole parts of a name
+        assert_eq!(cell(&fields, "number"), "");
+        assert_eq!(cell(&fields, "asset_tag"), "");
+    }
+
+    #[test]
+    fn test_csv_line() {
+        assert_eq!(csv_line(["1", "plain"].into_iter()), "1,plain");
+        assert_eq!(csv_line(["a, b"].into_iter()), "\"a, b\"");
+        assert_eq!(
+            csv_line(["Jane's \"work\" Mac"].into_iter()),
+            "\"Jane's \"\"work\"\" Mac\""
+        );
+        assert_eq!(
+            csv_line(["line one\nline two", "cr\r"].into_iter()),
+            "\"line one\nline two\",\"cr\r\""
+        );
+        assert_eq!(csv_line(["", ""].into_iter()), ",");
+    }
+
+    #[test]
+    fn test_write() {
+        let mut table = Table::new(vec![String::from("id"), String::from("name")]);
+        table.add(json!({"computers": [
+            {"id": 1, "name": "one"},
+            {"id": 2, "name": "two, \"quoted\""}
+        ]}));
+        table.add(json!({"computer": {"id": 3, "name": "three"}}));
+
+        let mut csv = Vec::new();
+        table.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "id,name\n1,one\n2,\"two, \"\"quoted\"\"\"\n3,three\n"
+        );
+
+        let mut aligned = Vec::new();
+        table.write_table(&mut aligned).unwrap();
+        assert_eq!(
+            String::from_utf8(aligned).unwrap(),
+            "id  name\n--  -------------\n1   one\n2   two, \"quoted\"\n3   three\n"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::pagination::PageQuery;
 use reqwest::Method;
 use std::collections::HashMap;
 
@@ -5,99 +6,187 @@ use std::collections::HashMap;
 pub enum ApiEndpoints {
     // Util
     TokenAuth,
+    TokenKeepAlive,
+    OAuthToken,
     // Computer
+    ComputerCreate,
     ComputerDelete,
+    ComputerUpdate,
     ComputerShow,
     ComputerSearch,
     ComputerList,
     // Mobile Device
+    MobileCreate,
     MobileDelete,
+    MobileUpdate,
     MobileShow,
     MobileSearch,
     MobileList,
     // User
+    UserCreate,
     UserDelete,
+    UserUpdate,
     UserShow,
     UserList,
     // Policy
+    PolicyCreate,
     PolicyDelete,
+    PolicyUpdate,
     PolicyShow,
     PolicyList,
     // Package
+    PackageCreate,
     PackageDelete,
+    PackageUpdate,
     PackageShow,
     PackageList,
     // Category
+    CategoryCreate,
     CategoryDelete,
+    CategoryUpdate,
     CategoryShow,
     CategoryList,
     // Department
+    DepartmentCreate,
     DepartmentDelete,
+    DepartmentUpdate,
     DepartmentShow,
     DepartmentList,
     // EBook
+    EbookCreate,
     EbookDelete,
+    EbookUpdate,
     EbookShow,
     EbookList,
     // Building
+    BuildingCreate,
     BuildingDelete,
+    BuildingUpdate,
     BuildingShow,
     BuildingList,
     // Mac Applications
+    MacAppCreate,
     MacAppDelete,
+    MacAppUpdate,
     MacAppShow,
     MacAppList,
     // Mobile Device Applications
+    MobileAppCreate,
     MobileAppDelete,
+    MobileAppUpdate,
     MobileAppShow,
     MobileAppList,
     // Scripts
+    ScriptCreate,
     ScriptDelete,
+    ScriptUpdate,
     ScriptShow,
     ScriptList,
     // Restricted Software
+    RestrictedSoftwareCreate,
     RestrictedSoftwareDelete,
+    RestrictedSoftwareUpdate,
     RestrictedSoftwareShow,
     RestrictedSoftwareList,
     // Printer
+    PrinterCreate,
     PrinterDelete,
+    PrinterUpdate,
     PrinterShow,
     PrinterList,
     // Patch
+    PatchPolicyCreate,
     PatchPolicyDelete,
+    PatchPolicyUpdate,
     PatchPolicyShow,
     PatchPolicyList,
     PatchReportListSoftware,
     PatchReportListComputer,
+    PatchSoftwareTitleCreate,
     PatchSoftwareTitleDelete,
+    PatchSoftwareTitleUpdate,
     PatchSoftwareTitleShow,
     PatchSoftwareTitleList,
     PatchAvailableTitleList,
+    PatchExternalSourceCreate,
     PatchExternalSourceShow,
     PatchExternalSourceList,
     PatchExternalSourceDelete,
+    PatchExternalSourceUpdate,
     PatchInternalSourceShow,
     PatchInternalSourceList,
     // Groups
+    GroupComputerCreate,
     GroupComputerDelete,
+    GroupComputerUpdate,
     GroupComputerShow,
     GroupComputerList,
+    GroupMobileCreate,
     GroupMobileDelete,
+    GroupMobileUpdate,
     GroupMobileShow,
     GroupMobileList,
+    GroupUserCreate,
     GroupUserDelete,
+    GroupUserUpdate,
     GroupUserShow,
     GroupUserList,
     // Advanced searches
+    AdvSearchComputerCreate,
     AdvSearchComputerDelete,
+    AdvSearchComputerUpdate,
     AdvSearchComputerShow,
     AdvSearchComputerList,
+    AdvSearchMobileCreate,
     AdvSearchMobileDelete,
+    AdvSearchMobileUpdate,
     AdvSearchMobileShow,
     AdvSearchMobileList,
+    AdvSearchUserCreate,
     AdvSearchUserDelete,
+    AdvSearchUserUpdate,
     AdvSearchUserShow,
     AdvSearchUserList,
+    // Jamf Pro API
+    // Computers
+    ProComputerShow,
+    ProComputerUpdate,
+    ProComputerDelete,
+    ProComputerList,
+    // Mobile Devices
+    ProMobileShow,
+    ProMobileUpdate,
+    ProMobileList,
+    // Buildings
+    ProBuildingCreate,
+    ProBuildingUpdate,
+    ProBuildingDelete,
+    ProBuildingShow,
+    ProBuildingList,
+    // Categories
+    ProCategoryCreate,
+    ProCategoryUpdate,
+    ProCategoryDelete,
+    ProCategoryShow,
+    ProCategoryList,
+    // Departments
+    ProDepartmentCreate,
+    ProDepartmentUpdate,
+    ProDepartmentDelete,
+    ProDepartmentShow,
+    ProDepartmentList,
+    // Scripts
+    ProScriptCreate,
+    ProScriptUpdate,
+    ProScriptDelete,
+    ProScriptShow,
+    ProScriptList,
+    // Packages
+    ProPackageCreate,
+    ProPackageUpdate,
+    ProPackageDelete,
+    ProPackageShow,
+    ProPackageList,
 }
 
 impl ApiEndpoints {
@@ -107,10 +196,26 @@ impl ApiEndpoints {
                 method: Method::POST,
                 url: "/api/auth/tokens",
             },
+            ApiEndpoints::TokenKeepAlive => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/api/auth/keepAlive",
+            },
+            ApiEndpoints::OAuthToken => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/api/oauth/token",
+            },
+            ApiEndpoints::ComputerCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/computers/id/0",
+            },
             ApiEndpoints::ComputerDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/computers/id/{id}",
             },
+            ApiEndpoints::ComputerUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/computers/id/{id}",
+            },
             ApiEndpoints::ComputerShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/computers/id/{id}",
@@ -123,10 +228,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/computers",
             },
+            ApiEndpoints::MobileCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/mobiledevices/id/0",
+            },
             ApiEndpoints::MobileDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/mobiledevices/id/{id}",
             },
+            ApiEndpoints::MobileUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/mobiledevices/id/{id}",
+            },
             ApiEndpoints::MobileShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/mobiledevices/id/{id}",
@@ -139,10 +252,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/mobiledevices",
             },
+            ApiEndpoints::UserCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/users/id/0",
+            },
             ApiEndpoints::UserDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/users/id/{id}",
             },
+            ApiEndpoints::UserUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/users/id/{id}",
+            },
             ApiEndpoints::UserShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/users/id/{id}",
@@ -151,10 +272,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/users",
             },
+            ApiEndpoints::PolicyCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/policies/id/0",
+            },
             ApiEndpoints::PolicyDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/policies/id/{id}",
             },
+            ApiEndpoints::PolicyUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/policies/id/{id}",
+            },
             ApiEndpoints::PolicyShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/policies/id/{id}",
@@ -163,10 +292,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/policies",
             },
+            ApiEndpoints::PackageCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/packages/id/0",
+            },
             ApiEndpoints::PackageDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/packages/id/{id}",
             },
+            ApiEndpoints::PackageUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/packages/id/{id}",
+            },
             ApiEndpoints::PackageShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/packages/id/{id}",
@@ -175,10 +312,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/packages",
             },
+            ApiEndpoints::CategoryCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/categories/id/0",
+            },
             ApiEndpoints::CategoryDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/categories/id/{id}",
             },
+            ApiEndpoints::CategoryUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/categories/id/{id}",
+            },
             ApiEndpoints::CategoryShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/categories/id/{id}",
@@ -187,10 +332,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/categories",
             },
+            ApiEndpoints::DepartmentCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/departments/id/0",
+            },
             ApiEndpoints::DepartmentDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/departments/id/{id}",
             },
+            ApiEndpoints::DepartmentUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/departments/id/{id}",
+            },
             ApiEndpoints::DepartmentShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/departments/id/{id}",
@@ -199,10 +352,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/departments",
             },
+            ApiEndpoints::EbookCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/ebooks/id/0",
+            },
             ApiEndpoints::EbookDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/ebooks/id/{id}",
             },
+            ApiEndpoints::EbookUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/ebooks/id/{id}",
+            },
             ApiEndpoints::EbookShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/ebooks/id/{id}",
@@ -211,10 +372,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/ebooks",
             },
+            ApiEndpoints::BuildingCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/buildings/id/0",
+            },
             ApiEndpoints::BuildingDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/buildings/id/{id}",
             },
+            ApiEndpoints::BuildingUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/buildings/id/{id}",
+            },
             ApiEndpoints::BuildingShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/buildings/id/{id}",
@@ -223,10 +392,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/buildings",
             },
+            ApiEndpoints::MacAppCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/macapplications/id/0",
+            },
             ApiEndpoints::MacAppDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/macapplications/id/{id}",
             },
+            ApiEndpoints::MacAppUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/macapplications/id/{id}",
+            },
             ApiEndpoints::MacAppShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/macapplications/id/{id}",
@@ -235,10 +412,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/macapplications",
             },
+            ApiEndpoints::MobileAppCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/mobiledeviceapplications/id/0",
+            },
             ApiEndpoints::MobileAppDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/mobiledeviceapplications/id/{id}",
             },
+            ApiEndpoints::MobileAppUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/mobiledeviceapplications/id/{id}",
+            },
             ApiEndpoints::MobileAppShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/mobiledeviceapplications/id/{id}",
@@ -247,10 +432,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/mobiledeviceapplications",
             },
+            ApiEndpoints::ScriptCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/scripts/id/0",
+            },
             ApiEndpoints::ScriptDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/scripts/id/{id}",
             },
+            ApiEndpoints::ScriptUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/scripts/id/{id}",
+            },
             ApiEndpoints::ScriptShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/scripts/id/{id}",
@@ -259,10 +452,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/scripts",
             },
+            ApiEndpoints::RestrictedSoftwareCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/restrictedsoftware/id/0",
+            },
             ApiEndpoints::RestrictedSoftwareDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/restrictedsoftware/id/{id}",
             },
+            ApiEndpoints::RestrictedSoftwareUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/restrictedsoftware/id/{id}",
+            },
             ApiEndpoints::RestrictedSoftwareShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/restrictedsoftware/id/{id}",
@@ -271,10 +472,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/restrictedsoftware",
             },
+            ApiEndpoints::PrinterCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/printers/id/0",
+            },
             ApiEndpoints::PrinterDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/printers/id/{id}",
             },
+            ApiEndpoints::PrinterUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/printers/id/{id}",
+            },
             ApiEndpoints::PrinterShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/printers/id/{id}",
@@ -283,10 +492,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/printers",
             },
+            ApiEndpoints::PatchPolicyCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/patchpolicies/softwaretitleconfig/id/{id}",
+            },
             ApiEndpoints::PatchPolicyDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/patchpolicies/id/{id}",
             },
+            ApiEndpoints::PatchPolicyUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/patchpolicies/id/{id}",
+            },
             ApiEndpoints::PatchPolicyShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/patchpolicies/id/{id}",
@@ -304,10 +521,18 @@ impl ApiEndpoints {
                 url:
                     "/JSSResource/patchreports/patchsoftwaretitleid/{id}/version/{software_version}",
             },
+            ApiEndpoints::PatchSoftwareTitleCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/patchsoftwaretitles/id/0",
+            },
             ApiEndpoints::PatchSoftwareTitleDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/patchsoftwaretitles/id/{id}",
             },
+            ApiEndpoints::PatchSoftwareTitleUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/patchsoftwaretitles/id/{id}",
+            },
             ApiEndpoints::PatchSoftwareTitleShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/patchsoftwaretitles/id/{id}",
@@ -320,6 +545,10 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/patchavailabletitles/sourceid/{id}",
             },
+            ApiEndpoints::PatchExternalSourceCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/patchexternalsources/id/0",
+            },
             ApiEndpoints::PatchExternalSourceShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/patchexternalsources/id/{id}",
@@ -332,6 +561,10 @@ impl ApiEndpoints {
                 method: Method::DELETE,
                 url: "/JSSResource/patchexternalsources/id/{id}",
             },
+            ApiEndpoints::PatchExternalSourceUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/patchexternalsources/id/{id}",
+            },
             ApiEndpoints::PatchInternalSourceShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/patchinternalsources/id/{id}",
@@ -340,10 +573,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/patchinternalsources",
             },
+            ApiEndpoints::GroupComputerCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/computergroups/id/0",
+            },
             ApiEndpoints::GroupComputerDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/computergroups/id/{id}",
             },
+            ApiEndpoints::GroupComputerUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/computergroups/id/{id}",
+            },
             ApiEndpoints::GroupComputerShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/computergroups/id/{id}",
@@ -352,10 +593,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/computergroups",
             },
+            ApiEndpoints::GroupMobileCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/mobiledevicegroups/id/0",
+            },
             ApiEndpoints::GroupMobileDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/mobiledevicegroups/id/{id}",
             },
+            ApiEndpoints::GroupMobileUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/mobiledevicegroups/id/{id}",
+            },
             ApiEndpoints::GroupMobileShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/mobiledevicegroups/id/{id}",
@@ -364,9 +613,17 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/mobiledevicegroups",
             },
+            ApiEndpoints::GroupUserCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/usergroups/id/0",
+            },
             ApiEndpoints::GroupUserDelete => ApiEndpointDetails {
                 method: Method::DELETE,
-                url: "/JSSResource/userroups/id/{id}",
+                url: "/JSSResource/usergroups/id/{id}",
+            },
+            ApiEndpoints::GroupUserUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/usergroups/id/{id}",
             },
             ApiEndpoints::GroupUserShow => ApiEndpointDetails {
                 method: Method::GET,
@@ -376,10 +633,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/usergroups",
             },
+            ApiEndpoints::AdvSearchComputerCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/advancedcomputersearches/id/0",
+            },
             ApiEndpoints::AdvSearchComputerDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/advancedcomputersearches/id/{id}",
             },
+            ApiEndpoints::AdvSearchComputerUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/advancedcomputersearches/id/{id}",
+            },
             ApiEndpoints::AdvSearchComputerShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/advancedcomputersearches/id/{id}",
@@ -388,10 +653,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/advancedcomputersearches",
             },
+            ApiEndpoints::AdvSearchMobileCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/advancedmobiledevicesearches/id/0",
+            },
             ApiEndpoints::AdvSearchMobileDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/advancedmobiledevicesearches/id/{id}",
             },
+            ApiEndpoints::AdvSearchMobileUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/advancedmobiledevicesearches/id/{id}",
+            },
             ApiEndpoints::AdvSearchMobileShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/advancedmobiledevicesearches/id/{id}",
@@ -400,10 +673,18 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/advancedmobiledevicesearches",
             },
+            ApiEndpoints::AdvSearchUserCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/JSSResource/advancedusersearches/id/0",
+            },
             ApiEndpoints::AdvSearchUserDelete => ApiEndpointDetails {
                 method: Method::DELETE,
                 url: "/JSSResource/advancedusersearches/id/{id}",
             },
+            ApiEndpoints::AdvSearchUserUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/JSSResource/advancedusersearches/id/{id}",
+            },
             ApiEndpoints::AdvSearchUserShow => ApiEndpointDetails {
                 method: Method::GET,
                 url: "/JSSResource/advancedusersearches/id/{id}",
@@ -412,22 +693,521 @@ impl ApiEndpoints {
                 method: Method::GET,
                 url: "/JSSResource/advancedusersearches",
             },
+            ApiEndpoints::ProComputerShow => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/computers-inventory-detail/{id}",
+            },
+            ApiEndpoints::ProComputerUpdate => ApiEndpointDetails {
+                method: Method::PATCH,
+                url: "/api/v1/computers-inventory-detail/{id}",
+            },
+            ApiEndpoints::ProComputerDelete => ApiEndpointDetails {
+                method: Method::DELETE,
+                url: "/api/v1/computers-inventory/{id}",
+            },
+            ApiEndpoints::ProComputerList => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/computers-inventory",
+            },
+            ApiEndpoints::ProMobileShow => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v2/mobile-devices/{id}/detail",
+            },
+            ApiEndpoints::ProMobileUpdate => ApiEndpointDetails {
+                method: Method::PATCH,
+                url: "/api/v2/mobile-devices/{id}",
+            },
+            ApiEndpoints::ProMobileList => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v2/mobile-devices",
+            },
+            ApiEndpoints::ProBuildingCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/api/v1/buildings",
+            },
+            ApiEndpoints::ProBuildingUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/api/v1/buildings/{id}",
+            },
+            ApiEndpoints::ProBuildingDelete => ApiEndpointDetails {
+                method: Method::DELETE,
+                url: "/api/v1/buildings/{id}",
+            },
+            ApiEndpoints::ProBuildingShow => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/buildings/{id}",
+            },
+            ApiEndpoints::ProBuildingList => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/buildings",
+            },
+            ApiEndpoints::ProCategoryCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/api/v1/categories",
+            },
+            ApiEndpoints::ProCategoryUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/api/v1/categories/{id}",
+            },
+            ApiEndpoints::ProCategoryDelete => ApiEndpointDetails {
+                method: Method::DELETE,
+                url: "/api/v1/categories/{id}",
+            },
+            ApiEndpoints::ProCategoryShow => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/categories/{id}",
+            },
+            ApiEndpoints::ProCategoryList => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/categories",
+            },
+            ApiEndpoints::ProDepartmentCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/api/v1/departments",
+            },
+            ApiEndpoints::ProDepartmentUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/api/v1/departments/{id}",
+            },
+            ApiEndpoints::ProDepartmentDelete => ApiEndpointDetails {
+                method: Method::DELETE,
+                url: "/api/v1/departments/{id}",
+            },
+            ApiEndpoints::ProDepartmentShow => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/departments/{id}",
+            },
+            ApiEndpoints::ProDepartmentList => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/departments",
+            },
+            ApiEndpoints::ProScriptCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/api/v1/scripts",
+            },
+            ApiEndpoints::ProScriptUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/api/v1/scripts/{id}",
+            },
+            ApiEndpoints::ProScriptDelete => ApiEndpointDetails {
+                method: Method::DELETE,
+                url: "/api/v1/scripts/{id}",
+            },
+            ApiEndpoints::ProScriptShow => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/scripts/{id}",
+            },
+            ApiEndpoints::ProScriptList => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/scripts",
+            },
+            ApiEndpoints::ProPackageCreate => ApiEndpointDetails {
+                method: Method::POST,
+                url: "/api/v1/packages",
+            },
+            ApiEndpoints::ProPackageUpdate => ApiEndpointDetails {
+                method: Method::PUT,
+                url: "/api/v1/packages/{id}",
+            },
+            ApiEndpoints::ProPackageDelete => ApiEndpointDetails {
+                method: Method::DELETE,
+                url: "/api/v1/packages/{id}",
+            },
+            ApiEndpoints::ProPackageShow => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/packages/{id}",
+            },
+            ApiEndpoints::ProPackageList => ApiEndpointDetails {
+                method: Method::GET,
+                url: "/api/v1/packages",
+            },
+        }
+    }
+
+    /// Returns the Jamf Pro API endpoint that performs the same operation as this Classic API
+    /// endpoint, if there is one
+    pub fn jamf_pro_equivalent(&self) -> Option<ApiEndpoints> {
+        match &self {
+            ApiEndpoints::ComputerShow => Some(ApiEndpoints::ProComputerShow),
+            ApiEndpoints::ComputerUpdate => Some(ApiEndpoints::ProComputerUpdate),
+            ApiEndpoints::ComputerDelete => Some(ApiEndpoints::ProComputerDelete),
+            ApiEndpoints::ComputerList => Some(ApiEndpoints::ProComputerList),
+            ApiEndpoints::MobileShow => Some(ApiEndpoints::ProMobileShow),
+            ApiEndpoints::MobileUpdate => Some(ApiEndpoints::ProMobileUpdate),
+            ApiEndpoints::MobileList => Some(ApiEndpoints::ProMobileList),
+            ApiEndpoints::BuildingCreate => Some(ApiEndpoints::ProBuildingCreate),
+            ApiEndpoints::BuildingUpdate => Some(ApiEndpoints::ProBuildingUpdate),
+            ApiEndpoints::BuildingDelete => Some(ApiEndpoints::ProBuildingDelete),
+            ApiEndpoints::BuildingShow => Some(ApiEndpoints::ProBuildingShow),
+            ApiEndpoints::BuildingList => Some(ApiEndpoints::ProBuildingList),
+            ApiEndpoints::CategoryCreate => Some(ApiEndpoints::ProCategoryCreate),
+            ApiEndpoints::CategoryUpdate => Some(ApiEndpoints::ProCategoryUpdate),
+            ApiEndpoints::CategoryDelete => Some(ApiEndpoints::ProCategoryDelete),
+            ApiEndpoints::CategoryShow => Some(ApiEndpoints::ProCategoryShow),
+            ApiEndpoints::CategoryList => Some(ApiEndpoints::ProCategoryList),
+            ApiEndpoints::DepartmentCreate => Some(ApiEndpoints::ProDepartmentCreate),
+            ApiEndpoints::DepartmentUpdate => Some(ApiEndpoints::ProDepartmentUpdate),
+            ApiEndpoints::DepartmentDelete => Some(ApiEndpoints::ProDepartmentDelete),
+            ApiEndpoints::DepartmentShow => Some(ApiEndpoints::ProDepartmentShow),
+            ApiEndpoints::DepartmentList => Some(ApiEndpoints::ProDepartmentList),
+            ApiEndpoints::ScriptCreate => Some(ApiEndpoints::ProScriptCreate),
+            ApiEndpoints::ScriptUpdate => Some(ApiEndpoints::ProScriptUpdate),
+            ApiEndpoints::ScriptDelete => Some(ApiEndpoints::ProScriptDelete),
+            ApiEndpoints::ScriptShow => Some(ApiEndpoints::ProScriptShow),
+            ApiEndpoints::ScriptList => Some(ApiEndpoints::ProScriptList),
+            ApiEndpoints::PackageCreate => Some(ApiEndpoints::ProPackageCreate),
+            ApiEndpoints::PackageUpdate => Some(ApiEndpoints::ProPackageUpdate),
+            ApiEndpoints::PackageDelete => Some(ApiEndpoints::ProPackageDelete),
+            ApiEndpoints::PackageShow => Some(ApiEndpoints::ProPackageShow),
+            ApiEndpoints::PackageList => Some(ApiEndpoints::ProPackageList),
+            _ => None,
         }
     }
 }
 
+#[derive(Clone)]
 pub enum Args {
     None,
+    /// Values for the named placeholders of the endpoint, e.g. "search_query"
     Strings(HashMap<&'static str, String>),
     Ids(Vec<String>),
+    /// Looks records up by something other than their id, e.g. name or serial number; Only valid
+    /// for endpoints that support the identifier
+    Lookup(Identifier, Vec<String>),
+    /// Walks every page of a Jamf Pro API list endpoint
+    Pages(PageQuery),
+}
+
+/// Identifiers records can be looked up by in place of their id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Identifier {
+    Name,
+    SerialNumber,
+    Udid,
+    MacAddress,
+}
+
+impl Identifier {
+    /// Path segment used in place of `id`, e.g. /JSSResource/computers/serialnumber/{serial}
+    pub fn path_segment(&self) -> &'static str {
+        match self {
+            Identifier::Name => "name",
+            Identifier::SerialNumber => "serialnumber",
+            Identifier::Udid => "udid",
+            Identifier::MacAddress => "macaddress",
+        }
+    }
+
+    /// Describes a record with the given value, e.g. "with serial number 'C02X'"
+    pub fn describe(&self, value: &str) -> String {
+        match self {
+            Identifier::Name => format!("named '{value}'"),
+            Identifier::SerialNumber => format!("with serial number '{value}'"),
+            Identifier::Udid => format!("with UDID '{value}'"),
+            Identifier::MacAddress => format!("with MAC address '{value}'"),
+        }
+    }
+}
+
+/// Kind of Classic API record, with the endpoints used to export, back up and restore records of
+/// that kind
+pub struct RecordType {
+    /// Element records are wrapped in, and referenced by from other records, e.g. computer_group
+    pub element: &'static str,
+    pub list: ApiEndpoints,
+    pub show: ApiEndpoints,
+    /// None if records can't be created by posting them to /id/0
+    pub create: Option<ApiEndpoints>,
 }
 
+impl RecordType {
+    /// Returns the collection records belong to, e.g. computergroups
+    pub fn entity(&self) -> &'static str {
+        self.list.usage().entity()
+    }
+
+    /// Finds the kind of record by its collection, e.g. policies
+    pub fn find(entity: &str) -> Option<&'static RecordType> {
+        RECORD_TYPES
+            .iter()
+            .find(|record_type| record_type.entity() == entity)
+    }
+}
+
+/// Every kind of Classic API record, in the order they're restored so records are created before
+/// the records referencing them, e.g. packages before the policies installing them
+pub static RECORD_TYPES: [RecordType; 24] = [
+    RecordType {
+        element: "category",
+        list: ApiEndpoints::CategoryList,
+        show: ApiEndpoints::CategoryShow,
+        create: Some(ApiEndpoints::CategoryCreate),
+    },
+    RecordType {
+        element: "building",
+        list: ApiEndpoints::BuildingList,
+        show: ApiEndpoints::BuildingShow,
+        create: Some(ApiEndpoints::BuildingCreate),
+    },
+    RecordType {
+        element: "department",
+        list: ApiEndpoints::DepartmentList,
+        show: ApiEndpoints::DepartmentShow,
+        create: Some(ApiEndpoints::DepartmentCreate),
+    },
+    RecordType {
+        element: "script",
+        list: ApiEndpoints::ScriptList,
+        show: ApiEndpoints::ScriptShow,
+        create: Some(ApiEndpoints::ScriptCreate),
+    },
+    RecordType {
+        element: "package",
+        list: ApiEndpoints::PackageList,
+        show: ApiEndpoints::PackageShow,
+        create: Some(ApiEndpoints::PackageCreate),
+    },
+    RecordType {
+        element: "printer",
+        list: ApiEndpoints::PrinterList,
+        show: ApiEndpoints::PrinterShow,
+        create: Some(ApiEndpoints::PrinterCreate),
+    },
+    RecordType {
+        element: "patch_external_source",
+        list: ApiEndpoints::PatchExternalSourceList,
+        show: ApiEndpoints::PatchExternalSourceShow,
+        create: Some(ApiEndpoints::PatchExternalSourceCreate),
+    },
+    RecordType {
+        element: "patch_internal_source",
+        list: ApiEndpoints::PatchInternalSourceList,
+        show: ApiEndpoints::PatchInternalSourceShow,
+        create: None,
+    },
+    RecordType {
+        element: "patch_software_title",
+        list: ApiEndpoints::PatchSoftwareTitleList,
+        show: ApiEndpoints::PatchSoftwareTitleShow,
+        create: Some(ApiEndpoints::PatchSoftwareTitleCreate),
+    },
+    RecordType {
+        element: "computer",
+        list: ApiEndpoints::ComputerList,
+        show: ApiEndpoints::ComputerShow,
+        create: Some(ApiEndpoints::ComputerCreate),
+    },
+    RecordType {
+        element: "mobile_device",
+        list: ApiEndpoints::MobileList,
+        show: ApiEndpoints::MobileShow,
+        create: Some(ApiEndpoints::MobileCreate),
+    },
+    RecordType {
+        element: "user",
+        list: ApiEndpoints::UserList,
+        show: ApiEndpoints::UserShow,
+        create: Some(ApiEndpoints::UserCreate),
+    },
+    RecordType {
+        element: "computer_group",
+        list: ApiEndpoints::GroupComputerList,
+        show: ApiEndpoints::GroupComputerShow,
+        create: Some(ApiEndpoints::GroupComputerCreate),
+    },
+    RecordType {
+        element: "mobile_device_group",
+        list: ApiEndpoints::GroupMobileList,
+        show: ApiEndpoints::GroupMobileShow,
+        create: Some(ApiEndpoints::GroupMobileCreate),
+    },
+    RecordType {
+        element: "user_group",
+        list: ApiEndpoints::GroupUserList,
+        show: ApiEndpoints::GroupUserShow,
+        create: Some(ApiEndpoints::GroupUserCreate),
+    },
+    RecordType {
+        element: "ebook",
+        list: ApiEndpoints::EbookList,
+        show: ApiEndpoints::EbookShow,
+        create: Some(ApiEndpoints::EbookCreate),
+    },
+    RecordType {
+        element: "mac_application",
+        list: ApiEndpoints::MacAppList,
+        show: ApiEndpoints::MacAppShow,
+        create: Some(ApiEndpoints::MacAppCreate),
+    },
+    RecordType {
+        element: "mobile_device_application",
+        list: ApiEndpoints::MobileAppList,
+        show: ApiEndpoints::MobileAppShow,
+        create: Some(ApiEndpoints::MobileAppCreate),
+    },
+    RecordType {
+        element: "restricted_software",
+        list: ApiEndpoints::RestrictedSoftwareList,
+        show: ApiEndpoints::RestrictedSoftwareShow,
+        create: Some(ApiEndpoints::RestrictedSoftwareCreate),
+    },
+    RecordType {
+        element: "policy",
+        list: ApiEndpoints::PolicyList,
+        show: ApiEndpoints::PolicyShow,
+        create: Some(ApiEndpoints::PolicyCreate),
+    },
+    RecordType {
+        element: "patch_policy",
+        list: ApiEndpoints::PatchPolicyList,
+        show: ApiEndpoints::PatchPolicyShow,
+        create: None,
+    },
+    RecordType {
+        element: "advanced_computer_search",
+        list: ApiEndpoints::AdvSearchComputerList,
+        show: ApiEndpoints::AdvSearchComputerShow,
+        create: Some(ApiEndpoints::AdvSearchComputerCreate),
+    },
+    RecordType {
+        element: "advanced_mobile_device_search",
+        list: ApiEndpoints::AdvSearchMobileList,
+        show: ApiEndpoints::AdvSearchMobileShow,
+        create: Some(ApiEndpoints::AdvSearchMobileCreate),
+    },
+    RecordType {
+        element: "advanced_user_search",
+        list: ApiEndpoints::AdvSearchUserList,
+        show: ApiEndpoints::AdvSearchUserShow,
+        create: Some(ApiEndpoints::AdvSearchUserCreate),
+    },
+];
+
+#[derive(Clone)]
 pub struct CommandDetails {
     pub args: Args,
     pub endpoint: ApiEndpointDetails,
+    pub body: Option<RequestBody>,
+    /// Sections of the record to fetch rather than the whole record; See `ApiEndpointDetails::subsets`
+    pub subset: Vec<&'static str>,
+}
+
+/// Document sent along with POST and PUT requests
+#[derive(Debug, Clone)]
+pub struct RequestBody {
+    pub content: String,
+    pub content_type: &'static str,
 }
 
+impl RequestBody {
+    /// Creates a body from an XML or JSON document, inferring the content type from its contents
+    pub fn new(content: String) -> Self {
+        let content_type = match content.trim_start().chars().next() {
+            Some('{') | Some('[') => "application/json",
+            _ => "application/xml",
+        };
+
+        RequestBody {
+            content,
+            content_type,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct ApiEndpointDetails {
     pub method: Method,
     pub url: &'static str,
 }
+
+const COMPUTER_SUBSETS: [&str; 12] = [
+    "General",
+    "Location",
+    "Purchasing",
+    "Peripherals",
+    "Hardware",
+    "Certificates",
+    "Security",
+    "Software",
+    "ExtensionAttributes",
+    "GroupsAccounts",
+    "iphones",
+    "ConfigurationProfiles",
+];
+
+const MOBILE_SUBSETS: [&str; 11] = [
+    "General",
+    "Location",
+    "Purchasing",
+    "Applications",
+    "Security",
+    "Network",
+    "Certificates",
+    "ConfigurationProfiles",
+    "ProvisioningProfiles",
+    "MobileDeviceGroups",
+    "ExtensionAttributes",
+];
+
+impl ApiEndpointDetails {
+    /// Sections of the record that can be fetched on their own, e.g.
+    /// /JSSResource/computers/id/{id}/subset/General&Hardware. Empty if the endpoint has no subsets
+    pub fn subsets(&self) -> &'static [&'static str] {
+        if self.method != Method::GET {
+            return &[];
+        }
+
+        match self.url {
+            "/JSSResource/computers/id/{id}" => &COMPUTER_SUBSETS,
+            "/JSSResource/mobiledevices/id/{id}" => &MOBILE_SUBSETS,
+            _ => &[],
+        }
+    }
+
+    /// Looks up the subset with the given name, ignoring case
+    pub fn subset(&self, name: &str) -> Option<&'static str> {
+        self.subsets()
+            .iter()
+            .find(|subset| subset.eq_ignore_ascii_case(name.trim()))
+            .copied()
+    }
+
+    /// Returns true if the endpoint belongs to the Jamf Pro API rather than the Classic API. The
+    /// Jamf Pro API only speaks JSON
+    pub fn is_jamf_pro_api(&self) -> bool {
+        self.url.starts_with("/api/")
+    }
+
+    /// Returns the collection of records the endpoint works with, e.g. computers for
+    /// /JSSResource/computers/id/{id} or mobile-devices for /api/v2/mobile-devices/{id}/detail.
+    /// The collection follows the version of Jamf Pro API URLs, ahead of any id and sub-resource
+    pub fn entity(&self) -> &'static str {
+        let url = self.url;
+        let position = if self.is_jamf_pro_api() { 3 } else { 2 };
+
+        url.split('/').nth(position).unwrap_or(url)
+    }
+
+    /// Returns true if Jamf Pro cuts the response short when asked for JSON. Listing available
+    /// patch titles as JSON only returns the first title, so these are requested as XML instead
+    pub fn truncates_json(&self) -> bool {
+        self.url.starts_with("/JSSResource/patchavailabletitles/")
+    }
+
+    /// Returns true if the record can be looked up by the given identifier in place of its id,
+    /// e.g. /JSSResource/policies/name/{name}. Patch management records can only be looked up by
+    /// id, and only computers and mobile devices by serial number, UDID or MAC address
+    pub fn supports_identifier(&self, identifier: Identifier) -> bool {
+        if !self.url.starts_with("/JSSResource/") || !self.url.ends_with("/id/{id}") {
+            return false;
+        }
+
+        match identifier {
+            Identifier::Name => !self.url.starts_with("/JSSResource/patch"),
+            Identifier::SerialNumber | Identifier::Udid | Identifier::MacAddress => {
+                self.url == "/JSSResource/computers/id/{id}"
+                    || self.url == "/JSSResource/mobiledevices/id/{id}"
+            }
+        }
+    }
+}
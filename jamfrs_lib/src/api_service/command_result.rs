@@ -0,0 +1,26 @@
+use super::error::JamfError;
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// Outcome of a single request sent while processing commands
+#[derive(Debug, Clone)]
+pub struct CommandResult {
+    /// Position of the request in the order the commands were given, starting at 0. Results
+    /// arrive in the order requests complete, which may differ
+    pub index: usize,
+    /// Id, name, serial number, etc. the request was sent for; None for commands without one
+    pub arg: Option<String>,
+    pub method: Method,
+    pub url: String,
+    /// Status code of the response; None if no response was received
+    pub status: Option<StatusCode>,
+    /// Time taken to complete the request, including any retries
+    pub elapsed: Duration,
+    pub body: Result<String, JamfError>,
+}
+
+impl CommandResult {
+    pub fn is_success(&self) -> bool {
+        self.body.is_ok()
+    }
+}
@@ -0,0 +1,106 @@
+use reqwest::StatusCode;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JamfError {
+    /// Credentials were rejected or a bearer token could not be obtained; The status is that of
+    /// the response rejecting them
+    Auth { status: StatusCode, reason: String },
+    /// The server responded with a non-success status code
+    Http { status: StatusCode, url: String },
+    /// No record exists with the name, serial number, etc. that was asked for
+    NotFound(String),
+    /// The request never completed, e.g. connection refused, TLS failure or timeout
+    Transport(String),
+    /// A response couldn't be parsed
+    Parse(String),
+    /// Invalid server address, arguments or client configuration
+    Config(String),
+    /// A record wasn't deleted as it couldn't be backed up first; The status is that of the
+    /// request fetching the record, if it got a response
+    Backup {
+        status: Option<StatusCode>,
+        reason: String,
+    },
+}
+
+impl fmt::Display for JamfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JamfError::Auth { reason, .. } => write!(f, "Authentication failed: {reason}"),
+            JamfError::Http { status, url } => write!(f, "{status} for {url}"),
+            JamfError::NotFound(msg) => write!(f, "{msg}"),
+            JamfError::Transport(msg) => write!(f, "Request failed: {msg}"),
+            JamfError::Parse(msg) => write!(f, "Failed to parse response: {msg}"),
+            JamfError::Config(msg) => write!(f, "Invalid configuration: {msg}"),
+            JamfError::Backup { reason, .. } => {
+                write!(
+                    f,
+                    "Not deleted as the record couldn't be backed up: {reason}"
+                )
+            }
+        }
+    }
+}
+
+impl JamfError {
+    /// Status code the server responded with, if the error came from a response
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            JamfError::Auth { status, .. } | JamfError::Http { status, .. } => Some(*status),
+            JamfError::NotFound(_) => Some(StatusCode::NOT_FOUND),
+            JamfError::Backup { status, .. } => *status,
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for JamfError {}
+
+impl From<reqwest::Error> for JamfError {
+    fn from(err: reqwest::Error) -> Self {
+        match (err.status(), err.url()) {
+            (Some(status), Some(url)) => JamfError::Http {
+                status,
+                url: url.path().to_string(),
+            },
+            _ => JamfError::Transport(err.to_string()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for JamfError {
+    fn from(err: serde_json::Error) -> Self {
+        JamfError::Parse(err.to_string())
+    }
+}
+
+impl From<quick_xml::DeError> for JamfError {
+    fn from(err: quick_xml::DeError) -> Self {
+        JamfError::Parse(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JamfError;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn test_status() {
+        let rejected = JamfError::Auth {
+            status: StatusCode::UNAUTHORIZED,
+            reason: String::from("Token rejected for /JSSResource/computers/id/1"),
+        };
+
+        assert_eq!(rejected.status(), Some(StatusCode::UNAUTHORIZED));
+        assert_eq!(
+            JamfError::NotFound(String::from("No computer named Jane's Mac")).status(),
+            Some(StatusCode::NOT_FOUND)
+        );
+        assert_eq!(
+            JamfError::Transport(String::from("timed out")).status(),
+            None
+        );
+    }
+}
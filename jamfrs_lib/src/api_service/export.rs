@@ -0,0 +1,102 @@
+use super::error::JamfError;
+use super::xml_json::xml_to_json;
+use serde_json::Value;
+
+/// Record as it appears in a Classic API list, e.g. `<policy><id>7</id><name>...</name></policy>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListedRecord {
+    pub id: String,
+    /// Empty for records listed without a name
+    pub name: String,
+}
+
+/// Reads the id and name of every record in a Classic API list response
+pub fn listed_records(list: &str) -> Result<Vec<ListedRecord>, JamfError> {
+    let list = xml_to_json(list)?;
+
+    // Lists hold a <size> element counting their records, so they're always converted to arrays
+    let records = match list.as_object().and_then(|root| root.values().next()) {
+        Some(Value::Array(records)) => records,
+        _ => return Err(JamfError::Parse(format!("Not a list of records: {list}"))),
+    };
+
+    Ok(records
+        .iter()
+        .filter_map(|record| {
+            let field = |name| record.get(name).and_then(Value::as_str);
+
+            Some(ListedRecord {
+                id: field("id")?.to_string(),
+                name: field("name").unwrap_or_default().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Name of the file an exported record is written to, e.g. 7-Install Chrome.xml. Characters that
+/// aren't safe in file names on every platform are replaced with underscores
+pub fn file_name(record: &ListedRecord, extension: &str) -> String {
+    let name: String = record
+        .name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    match name.as_str() {
+        "" => format!("{}.{extension}", record.id),
+        name => format!("{}-{name}.{extension}", record.id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{file_name, listed_records, ListedRecord};
+
+    fn listed(id: &str, name: &str) -> ListedRecord {
+        ListedRecord {
+            id: id.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_listed_records() {
+        assert_eq!(
+            listed_records(
+                "<policies><size>2</size><policy><id>7</id><name>Install Chrome</name></policy>\
+                <policy><id>8</id><name>Install Firefox</name></policy></policies>"
+            )
+            .unwrap(),
+            vec![
+                listed("7", "Install Chrome"),
+                listed("8", "Install Firefox")
+            ]
+        );
+        assert_eq!(
+            listed_records("<scripts><size>1</size><script><id>3</id></script></scripts>").unwrap(),
+            vec![listed("3", "")]
+        );
+        assert!(listed_records("<scripts><size>0</size></scripts>")
+            .unwrap()
+            .is_empty());
+        assert!(listed_records("<script><id>3</id></script>").is_err());
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            file_name(&listed("7", "Install Chrome"), "xml"),
+            "7-Install Chrome.xml"
+        );
+        assert_eq!(
+            file_name(&listed("8", "Office 365: Word/Excel"), "json"),
+            "8-Office 365_ Word_Excel.json"
+        );
+        assert_eq!(file_name(&listed("9", " "), "xml"), "9.xml");
+    }
+}
@@ -0,0 +1,410 @@
+use super::error::JamfError;
+use std::fmt;
+use std::str::FromStr;
+
+/// RSQL filter expression accepted by the `filter` parameter of Jamf Pro API list endpoints.
+/// Values are quoted as necessary when rendered; URL-encoding is left to the request builder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    Comparison {
+        field: String,
+        operator: Operator,
+        values: Vec<String>,
+    },
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    In,
+    NotIn,
+}
+
+impl Operator {
+    // Longer operators come first so "<=" isn't mistaken for "<" when parsing
+    const ALL: [Operator; 8] = [
+        Operator::In,
+        Operator::NotIn,
+        Operator::Equal,
+        Operator::NotEqual,
+        Operator::LessThanOrEqual,
+        Operator::GreaterThanOrEqual,
+        Operator::LessThan,
+        Operator::GreaterThan,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+            Operator::LessThan => "<",
+            Operator::LessThanOrEqual => "<=",
+            Operator::GreaterThan => ">",
+            Operator::GreaterThanOrEqual => ">=",
+            Operator::In => "=in=",
+            Operator::NotIn => "=out=",
+        }
+    }
+}
+
+/// Field of a record being filtered on, used to build comparisons
+pub struct Field(String);
+
+impl Field {
+    pub fn eq(self, value: impl Into<String>) -> Filter {
+        self.compare(Operator::Equal, vec![value.into()])
+    }
+
+    pub fn ne(self, value: impl Into<String>) -> Filter {
+        self.compare(Operator::NotEqual, vec![value.into()])
+    }
+
+    pub fn lt(self, value: impl Into<String>) -> Filter {
+        self.compare(Operator::LessThan, vec![value.into()])
+    }
+
+    pub fn le(self, value: impl Into<String>) -> Filter {
+        self.compare(Operator::LessThanOrEqual, vec![value.into()])
+    }
+
+    pub fn gt(self, value: impl Into<String>) -> Filter {
+        self.compare(Operator::GreaterThan, vec![value.into()])
+    }
+
+    pub fn ge(self, value: impl Into<String>) -> Filter {
+        self.compare(Operator::GreaterThanOrEqual, vec![value.into()])
+    }
+
+    pub fn is_in<I: IntoIterator<Item = S>, S: Into<String>>(self, values: I) -> Filter {
+        self.compare(Operator::In, values.into_iter().map(Into::into).collect())
+    }
+
+    pub fn not_in<I: IntoIterator<Item = S>, S: Into<String>>(self, values: I) -> Filter {
+        self.compare(
+            Operator::NotIn,
+            values.into_iter().map(Into::into).collect(),
+        )
+    }
+
+    fn compare(self, operator: Operator, values: Vec<String>) -> Filter {
+        Filter::Comparison {
+            field: self.0,
+            operator,
+            values,
+        }
+    }
+}
+
+impl Filter {
+    /// Starts a comparison on the given field, e.g. `Filter::field("general.name").eq("Mac")`
+    pub fn field(name: impl Into<String>) -> Field {
+        Field(name.into())
+    }
+
+    /// Combines this filter with another, requiring both to match
+    pub fn and(self, other: Filter) -> Filter {
+        match self {
+            Filter::And(mut filters) => {
+                filters.push(other);
+                Filter::And(filters)
+            }
+            filter => Filter::And(vec![filter, other]),
+        }
+    }
+
+    /// Combines this filter with another, requiring either to match
+    pub fn or(self, other: Filter) -> Filter {
+        match self {
+            Filter::Or(mut filters) => {
+                filters.push(other);
+                Filter::Or(filters)
+            }
+            filter => Filter::Or(vec![filter, other]),
+        }
+    }
+
+    /// Requires every filter to match
+    pub fn all(filters: Vec<Filter>) -> Filter {
+        Filter::And(filters)
+    }
+
+    /// Requires at least one filter to match
+    pub fn any(filters: Vec<Filter>) -> Filter {
+        Filter::Or(filters)
+    }
+
+    fn fmt_group(f: &mut fmt::Formatter<'_>, filters: &[Filter], separator: &str) -> fmt::Result {
+        for (i, filter) in filters.iter().enumerate() {
+            if i > 0 {
+                f.write_str(separator)?;
+            }
+
+            // Only OR groups need parentheses, since AND binds more tightly
+            match filter {
+                Filter::Or(_) if separator == ";" => write!(f, "({filter})")?,
+                _ => write!(f, "{filter}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn quote(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value.chars().any(|c| {
+            c.is_whitespace()
+                || matches!(
+                    c,
+                    '"' | '\'' | '(' | ')' | ';' | ',' | '=' | '!' | '~' | '<' | '>' | '\\'
+                )
+        });
+
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::Comparison {
+                field,
+                operator,
+                values,
+            } => match operator {
+                Operator::In | Operator::NotIn => write!(
+                    f,
+                    "{field}{}({})",
+                    operator.as_str(),
+                    values
+                        .iter()
+                        .map(|v| quote(v))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                ),
+                _ => write!(
+                    f,
+                    "{field}{}{}",
+                    operator.as_str(),
+                    quote(values.first().map_or("", String::as_str))
+                ),
+            },
+            Filter::And(filters) => Filter::fmt_group(f, filters, ";"),
+            Filter::Or(filters) => Filter::fmt_group(f, filters, ","),
+        }
+    }
+}
+
+/// Parses a single comparison such as `general.name==Jane's Mac` or `id=in=(1,2,3)`. Values
+/// don't need to be quoted
+impl FromStr for Filter {
+    type Err = JamfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, operator) = Operator::ALL
+            .iter()
+            .filter_map(|op| s.find(op.as_str()).map(|position| (position, *op)))
+            .min_by_key(|(position, _)| *position)
+            .ok_or_else(|| {
+                JamfError::Config(format!(
+                    "No comparison operator in filter '{s}'; Expected one of ==, !=, <, <=, >, >=, =in=, =out="
+                ))
+            })?;
+
+        let field = s[..position].trim();
+        let value = s[position + operator.as_str().len()..].trim();
+
+        if field.is_empty() {
+            return Err(JamfError::Config(format!("No field in filter '{s}'")));
+        }
+
+        let values = match operator {
+            Operator::In | Operator::NotIn => split_list(
+                value
+                    .strip_prefix('(')
+                    .and_then(|list| list.strip_suffix(')'))
+                    .unwrap_or(value),
+            )
+            .into_iter()
+            .map(unquote)
+            .collect(),
+            _ => vec![unquote(value)],
+        };
+
+        Ok(Filter::Comparison {
+            field: field.to_string(),
+            operator,
+            values,
+        })
+    }
+}
+
+/// Splits the values of an =in= or =out= list on the commas outside of quoted values, so
+/// `"Jane's Mac, 2",Other` is two values
+fn split_list(list: &str) -> Vec<&str> {
+    let mut values = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (position, c) in list.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                values.push(&list[start..position]);
+                start = position + 1;
+            }
+            _ => {}
+        }
+    }
+    values.push(&list[start..]);
+
+    values
+}
+
+/// Removes the quotes `quote` puts around a value, along with the escaping within them
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+
+    match value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(quoted) => {
+            let mut unquoted = String::with_capacity(quoted.len());
+            let mut chars = quoted.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => unquoted.extend(chars.next()),
+                    c => unquoted.push(c),
+                }
+            }
+            unquoted
+        }
+        None => value.trim_matches('"').to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Filter, Operator};
+
+    #[test]
+    fn test_comparisons() {
+        assert_eq!(
+            Filter::field("general.name").eq("Mac").to_string(),
+            "general.name==Mac"
+        );
+        assert_eq!(Filter::field("id").ne("5").to_string(), "id!=5");
+        assert_eq!(
+            Filter::field("general.lastContactTime")
+                .lt("2024-01-01T00:00:00Z")
+                .to_string(),
+            "general.lastContactTime<2024-01-01T00:00:00Z"
+        );
+        assert_eq!(Filter::field("id").ge("10").to_string(), "id>=10");
+        assert_eq!(
+            Filter::field("id").is_in(["1", "2", "3"]).to_string(),
+            "id=in=(1,2,3)"
+        );
+    }
+
+    #[test]
+    fn test_values_quoted_when_necessary() {
+        assert_eq!(
+            Filter::field("general.name")
+                .eq("Jane's MacBook")
+                .to_string(),
+            "general.name==\"Jane's MacBook\""
+        );
+        assert_eq!(
+            Filter::field("general.name").eq("say \"hi\"").to_string(),
+            "general.name==\"say \\\"hi\\\"\""
+        );
+        assert_eq!(
+            Filter::field("general.name").eq("").to_string(),
+            "general.name==\"\""
+        );
+        assert_eq!(
+            Filter::field("name").is_in(["a,b", "c"]).to_string(),
+            "name=in=(\"a,b\",c)"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for filter in [
+            Filter::field("name").is_in(["a,b", "c"]),
+            Filter::field("name").not_in(["say \"hi\", bye", "back\\slash", ""]),
+            Filter::field("general.name").eq("say \"hi\""),
+        ] {
+            assert_eq!(filter.to_string().parse::<Filter>().unwrap(), filter);
+        }
+    }
+
+    #[test]
+    fn test_grouping() {
+        let filter = Filter::field("a")
+            .eq("1")
+            .and(Filter::field("b").eq("2").or(Filter::field("c").eq("3")));
+        assert_eq!(filter.to_string(), "a==1;(b==2,c==3)");
+
+        let filter = Filter::field("a")
+            .eq("1")
+            .and(Filter::field("b").eq("2"))
+            .or(Filter::field("c").eq("3"));
+        assert_eq!(filter.to_string(), "a==1;b==2,c==3");
+
+        let filter = Filter::any(vec![Filter::field("a").eq("1"), Filter::field("b").eq("2")]);
+        assert_eq!(filter.to_string(), "a==1,b==2");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "general.lastContactTime<2024-01-01"
+                .parse::<Filter>()
+                .unwrap(),
+            Filter::field("general.lastContactTime").lt("2024-01-01")
+        );
+        assert_eq!(
+            "id <= 5".parse::<Filter>().unwrap(),
+            Filter::field("id").le("5")
+        );
+        assert_eq!(
+            "general.name==Jane's Mac".parse::<Filter>().unwrap(),
+            Filter::field("general.name").eq("Jane's Mac")
+        );
+        assert_eq!(
+            "id=in=(1, 2)".parse::<Filter>().unwrap(),
+            Filter::field("id").is_in(["1", "2"])
+        );
+        assert!(matches!(
+            "id=out=3".parse::<Filter>().unwrap(),
+            Filter::Comparison {
+                operator: Operator::NotIn,
+                ..
+            }
+        ));
+        assert_eq!(
+            "general.name=in=(\"Jane's Mac, 2\",Other)"
+                .parse::<Filter>()
+                .unwrap(),
+            Filter::field("general.name").is_in(["Jane's Mac, 2", "Other"])
+        );
+        assert!("general.name".parse::<Filter>().is_err());
+        assert!("==Mac".parse::<Filter>().is_err());
+    }
+}
@@ -0,0 +1,420 @@
+use super::api_endpoints::ApiEndpoints;
+use super::error::JamfError;
+use serde::de::{DeserializeOwned, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A record returned by the Classic API which can be fetched by id
+pub trait Record: DeserializeOwned + Serialize {
+    /// Root element of the XML document, which is also the key wrapping the JSON document
+    const ROOT: &'static str;
+    /// Endpoint used to fetch a single record by id
+    const SHOW: ApiEndpoints;
+}
+
+/// Parses a Classic API XML document into a record
+pub fn from_xml<T: Record>(xml: &str) -> Result<T, JamfError> {
+    Ok(quick_xml::de::from_str(xml)?)
+}
+
+/// Parses a Classic API JSON document, e.g. `{"computer": {...}}`, into a record
+pub fn from_json<T: Record>(json: &str) -> Result<T, JamfError> {
+    let mut document: Value = serde_json::from_str(json)?;
+
+    match document.get_mut(T::ROOT) {
+        Some(record) => Ok(serde_json::from_value(record.take())?),
+        None => Err(JamfError::Parse(format!(
+            "Missing '{}' in document",
+            T::ROOT
+        ))),
+    }
+}
+
+/// Renders a record as an XML document that can be sent to the Classic API to create or update it
+pub fn to_xml<T: Record>(record: &T) -> Result<String, JamfError> {
+    WRITING_XML.with(|writing_xml| writing_xml.set(true));
+    let xml = quick_xml::se::to_string_with_root(T::ROOT, record);
+    WRITING_XML.with(|writing_xml| writing_xml.set(false));
+
+    xml.map_err(|err| JamfError::Parse(err.to_string()))
+}
+
+thread_local! {
+    /// Set while [`to_xml`] is rendering a record, so nested lists are written the way XML expects
+    /// them rather than as the plain arrays used in JSON
+    static WRITING_XML: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Nested records are wrapped in an element holding a `<size>` and one element per record in XML,
+/// but are a plain array in JSON. This accepts either
+fn list<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct ListVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for ListVisitor<T> {
+        type Value = Vec<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a list of records")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut records = Vec::new();
+            while let Some(record) = seq.next_element()? {
+                records.push(record);
+            }
+
+            Ok(records)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut records = Vec::new();
+            while let Some(key) = map.next_key::<String>()? {
+                if key == "size" {
+                    map.next_value::<IgnoredAny>()?;
+                } else {
+                    records.push(map.next_value()?);
+                }
+            }
+
+            Ok(records)
+        }
+
+        // An empty element, e.g. <computers/>
+        fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+            Ok(Vec::new())
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E> {
+            Ok(Vec::new())
+        }
+    }
+
+    deserializer.deserialize_any(ListVisitor(PhantomData))
+}
+
+/// Reference to another record, e.g. the category of a policy. An id of -1 means none is assigned
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Reference {
+    pub id: i32,
+    pub name: String,
+}
+
+/// Where a device is assigned and who it's assigned to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Location {
+    pub username: String,
+    pub realname: String,
+    pub email_address: String,
+    pub position: String,
+    pub phone_number: String,
+    pub department: String,
+    pub building: String,
+    pub room: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Computer {
+    pub general: ComputerGeneral,
+    pub location: Location,
+    pub hardware: ComputerHardware,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComputerGeneral {
+    pub id: u32,
+    pub name: String,
+    pub mac_address: String,
+    pub serial_number: String,
+    pub udid: String,
+    pub ip_address: String,
+    pub asset_tag: String,
+    pub last_contact_time: String,
+    pub report_date: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComputerHardware {
+    pub make: String,
+    pub model: String,
+    pub model_identifier: String,
+    pub os_name: String,
+    pub os_version: String,
+    pub os_build: String,
+    pub processor_type: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MobileDevice {
+    pub general: MobileDeviceGeneral,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MobileDeviceGeneral {
+    pub id: u32,
+    pub name: String,
+    pub serial_number: String,
+    pub udid: String,
+    pub wifi_mac_address: String,
+    pub asset_tag: String,
+    pub model: String,
+    pub os_version: String,
+    pub phone_number: String,
+    pub last_inventory_update: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct User {
+    pub id: u32,
+    pub name: String,
+    pub full_name: String,
+    pub email: String,
+    pub email_address: String,
+    pub phone_number: String,
+    pub position: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Policy {
+    pub general: PolicyGeneral,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyGeneral {
+    pub id: u32,
+    pub name: String,
+    pub enabled: bool,
+    pub trigger: String,
+    pub frequency: String,
+    pub category: Reference,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Package {
+    pub id: u32,
+    pub name: String,
+    pub category: String,
+    pub filename: String,
+    pub info: String,
+    pub notes: String,
+    pub priority: i32,
+    pub reboot_required: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Script {
+    pub id: u32,
+    pub name: String,
+    pub category: String,
+    pub filename: String,
+    pub info: String,
+    pub notes: String,
+    /// When the script runs relative to other policy actions, e.g. Before or After
+    pub priority: String,
+    pub script_contents: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Category {
+    pub id: u32,
+    pub name: String,
+    pub priority: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Building {
+    pub id: u32,
+    pub name: String,
+    pub street_address1: String,
+    pub street_address2: String,
+    pub city: String,
+    pub state_province: String,
+    pub zip_postal_code: String,
+    pub country: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Department {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComputerGroup {
+    pub id: u32,
+    pub name: String,
+    pub is_smart: bool,
+    pub site: Reference,
+    #[serde(deserialize_with = "list", serialize_with = "computer_list")]
+    pub computers: Vec<Reference>,
+}
+
+// Members are wrapped with a <size> element in XML, but are a plain array in JSON, so a group
+// written with serde_json reads back through from_json
+fn computer_list<S: Serializer>(computers: &[Reference], serializer: S) -> Result<S::Ok, S::Error> {
+    if !WRITING_XML.with(Cell::get) {
+        return computers.serialize(serializer);
+    }
+
+    #[derive(Serialize)]
+    struct Computers<'a> {
+        size: usize,
+        computer: &'a [Reference],
+    }
+
+    Computers {
+        size: computers.len(),
+        computer: computers,
+    }
+    .serialize(serializer)
+}
+
+macro_rules! record {
+    ($record:ty, $root:literal, $show:expr) => {
+        impl Record for $record {
+            const ROOT: &'static str = $root;
+            const SHOW: ApiEndpoints = $show;
+        }
+    };
+}
+
+record!(Computer, "computer", ApiEndpoints::ComputerShow);
+record!(MobileDevice, "mobile_device", ApiEndpoints::MobileShow);
+record!(User, "user", ApiEndpoints::UserShow);
+record!(Policy, "policy", ApiEndpoints::PolicyShow);
+record!(Package, "package", ApiEndpoints::PackageShow);
+record!(Script, "script", ApiEndpoints::ScriptShow);
+record!(Category, "category", ApiEndpoints::CategoryShow);
+record!(Building, "building", ApiEndpoints::BuildingShow);
+record!(Department, "department", ApiEndpoints::DepartmentShow);
+record!(
+    ComputerGroup,
+    "computer_group",
+    ApiEndpoints::GroupComputerShow
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{from_json, from_xml, to_xml, Computer, ComputerGroup, Policy, Reference};
+    use serde_json::json;
+
+    #[test]
+    fn test_computer_from_xml_and_json() {
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?><computer><general><id>12</id>\
+            <name>Jane's Mac</name><serial_number>C02XYZ</serial_number><udid>ABC-123</udid>\
+            <remote_management><managed>true</managed></remote_management></general>\
+            <location><username>jane</username><building/></location>\
+            <hardware><os_version>14.5</os_version></hardware></computer>";
+        let json = r#"{"computer":{"general":{"id":12,"name":"Jane's Mac","serial_number":"C02XYZ",
+            "udid":"ABC-123","remote_management":{"managed":true}},"location":{"username":"jane",
+            "building":""},"hardware":{"os_version":"14.5","total_ram":16384}}}"#;
+
+        let from_xml: Computer = from_xml(xml).unwrap();
+        let from_json: Computer = from_json(json).unwrap();
+
+        assert_eq!(from_xml, from_json);
+        assert_eq!(from_xml.general.id, 12);
+        assert_eq!(from_xml.general.name, "Jane's Mac");
+        assert_eq!(from_xml.location.username, "jane");
+        assert_eq!(from_xml.hardware.os_version, "14.5");
+    }
+
+    #[test]
+    fn test_nested_records() {
+        let xml = "<policy><general><id>3</id><name>Install</name><enabled>true</enabled>\
+            <category><id>-1</id><name>No category assigned</name></category></general></policy>";
+        let policy: Policy = from_xml(xml).unwrap();
+
+        assert!(policy.general.enabled);
+        assert_eq!(policy.general.category.id, -1);
+
+        let xml = "<computer_group><id>5</id><name>Lab</name><is_smart>false</is_smart>\
+            <computers><size>2</size><computer><id>1</id><name>a</name></computer>\
+            <computer><id>2</id><name>b</name></computer></computers></computer_group>";
+        let json = r#"{"computer_group":{"id":5,"name":"Lab","is_smart":false,
+            "computers":[{"id":1,"name":"a"},{"id":2,"name":"b"}]}}"#;
+
+        let group: ComputerGroup = from_xml(xml).unwrap();
+        assert_eq!(group, from_json(json).unwrap());
+        assert_eq!(
+            group.computers,
+            vec![
+                Reference {
+                    id: 1,
+                    name: String::from("a")
+                },
+                Reference {
+                    id: 2,
+                    name: String::from("b")
+                }
+            ]
+        );
+
+        let empty: ComputerGroup =
+            from_xml("<computer_group><id>6</id><computers/></computer_group>").unwrap();
+        assert!(empty.computers.is_empty());
+    }
+
+    #[test]
+    fn test_to_xml_round_trip() {
+        let group = ComputerGroup {
+            id: 5,
+            name: String::from("Lab & Friends"),
+            computers: vec![Reference {
+                id: 1,
+                name: String::from("a"),
+            }],
+            ..ComputerGroup::default()
+        };
+
+        let xml = to_xml(&group).unwrap();
+        assert!(xml.starts_with("<computer_group><id>5</id><name>Lab &amp; Friends</name>"));
+        assert!(xml.contains("<computers><size>1</size><computer><id>1</id>"));
+        assert_eq!(from_xml::<ComputerGroup>(&xml).unwrap(), group);
+    }
+
+    #[test]
+    fn test_to_json_round_trip() {
+        let group = ComputerGroup {
+            id: 5,
+            name: String::from("Lab"),
+            computers: vec![Reference {
+                id: 1,
+                name: String::from("a"),
+            }],
+            ..ComputerGroup::default()
+        };
+
+        let json = serde_json::to_string(&json!({ "computer_group": group })).unwrap();
+        assert!(json.contains(r#""computers":[{"id":1,"name":"a"}]"#));
+        assert_eq!(from_json::<ComputerGroup>(&json).unwrap(), group);
+    }
+
+    #[test]
+    fn test_missing_root() {
+        assert!(from_json::<Computer>(r#"{"policy":{}}"#).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use super::error::JamfError;
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::Value;
+
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Controls how records are fetched from Jamf Pro API list endpoints
+#[derive(Debug, Clone)]
+pub struct PageQuery {
+    /// Number of records requested per page
+    pub page_size: u32,
+    /// Sort criteria in the format property:asc or property:desc
+    pub sort: Vec<String>,
+    /// RSQL expression used to filter the records
+    pub filter: Option<String>,
+    /// Maximum number of records to fetch across all pages
+    pub limit: Option<usize>,
+}
+
+impl Default for PageQuery {
+    fn default() -> Self {
+        PageQuery {
+            page_size: DEFAULT_PAGE_SIZE,
+            sort: Vec::new(),
+            filter: None,
+            limit: None,
+        }
+    }
+}
+
+impl PageQuery {
+    /// Builds the URL for the given page (starting at 0) of the list endpoint at `base_url`
+    pub fn page_url(&self, base_url: &str, page: usize) -> Result<String, JamfError> {
+        let mut params = vec![
+            ("page", page.to_string()),
+            ("page-size", self.requested_page_size().to_string()),
+        ];
+
+        if !self.sort.is_empty() {
+            params.push(("sort", self.sort.join(",")));
+        }
+
+        if let Some(filter) = &self.filter {
+            params.push(("filter", filter.clone()));
+        }
+
+        Url::parse_with_params(base_url, &params)
+            .map(String::from)
+            .map_err(|err| JamfError::Config(format!("Invalid URL {base_url}: {err}")))
+    }
+
+    /// Number of pages needed to fetch `records` records
+    pub fn page_count(&self, records: usize) -> usize {
+        records.div_ceil(self.requested_page_size() as usize)
+    }
+
+    /// Page size sent to the server, which is the limit when it's smaller than a page so no more
+    /// records are fetched than are returned. Every page must be the same size for the pages to
+    /// line up, so a limit spanning several pages may still fetch some extra records on the last
+    fn requested_page_size(&self) -> u32 {
+        let page_size = self.page_size.max(1);
+
+        match self.limit {
+            Some(limit) if limit < page_size as usize => (limit as u32).max(1),
+            _ => page_size,
+        }
+    }
+}
+
+/// A single page of results returned by a Jamf Pro API list endpoint
+#[derive(Debug, Deserialize)]
+pub struct Page {
+    #[serde(rename = "totalCount")]
+    pub total_count: usize,
+    pub results: Vec<Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PageQuery;
+
+    #[test]
+    fn test_page_url() {
+        let query = PageQuery {
+            page_size: 50,
+            sort: vec![String::from("general.name:asc"), String::from("id:desc")],
+            filter: Some(String::from("general.name==\"Jane's Mac\"")),
+            limit: None,
+        };
+
+        let url = query
+            .page_url(
+                "https://test.jamfcloud.com:443/api/v1/computers-inventory",
+                2,
+            )
+            .unwrap();
+
+        assert_eq!(
+            url,
+            "https://test.jamfcloud.com/api/v1/computers-inventory?page=2&page-size=50\
+            &sort=general.name%3Aasc%2Cid%3Adesc&filter=general.name%3D%3D%22Jane%27s+Mac%22"
+        );
+    }
+
+    #[test]
+    fn test_page_count() {
+        let query = PageQuery {
+            page_size: 100,
+            ..PageQuery::default()
+        };
+
+        assert_eq!(query.page_count(0), 0);
+        assert_eq!(query.page_count(1), 1);
+        assert_eq!(query.page_count(100), 1);
+        assert_eq!(query.page_count(101), 2);
+    }
+
+    #[test]
+    fn test_limit_smaller_than_page() {
+        let query = PageQuery {
+            page_size: 100,
+            limit: Some(10),
+            ..PageQuery::default()
+        };
+
+        assert_eq!(
+            query
+                .page_url("https://test.jamfcloud.com/api/v1/scripts", 0)
+                .unwrap(),
+            "https://test.jamfcloud.com/api/v1/scripts?page=0&page-size=10"
+        );
+        assert_eq!(query.page_count(10), 1);
+
+        // Larger limits keep the page size, so later pages start where they should
+        let query = PageQuery {
+            limit: Some(250),
+            ..query
+        };
+        assert_eq!(
+            query
+                .page_url("https://test.jamfcloud.com/api/v1/scripts", 2)
+                .unwrap(),
+            "https://test.jamfcloud.com/api/v1/scripts?page=2&page-size=100"
+        );
+    }
+}
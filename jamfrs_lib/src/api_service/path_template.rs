@@ -0,0 +1,118 @@
+use super::error::JamfError;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+// Everything but unreserved characters is encoded, so values containing '/' or '?' stay in their
+// own path segment
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Percent-encodes a value so it can be used as a single path segment
+pub fn encode_segment(value: &str) -> String {
+    utf8_percent_encode(value, PATH_SEGMENT).to_string()
+}
+
+/// Fills the `{placeholders}` of a path such as /JSSResource/computers/id/{id} with the given
+/// values, percent-encoding each of them. Every placeholder must be filled, and every value must
+/// have a placeholder to fill
+pub fn render<'a, I>(template: &str, values: I) -> Result<String, JamfError>
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let values: Vec<(&str, &str)> = values.into_iter().collect();
+    let mut used = vec![false; values.len()];
+    let mut path = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        if rest[..start].contains('}') {
+            break;
+        }
+
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| JamfError::Config(format!("Unclosed placeholder in {template}")))?;
+        let placeholder = &rest[start + 1..end];
+
+        let index = values
+            .iter()
+            .position(|(name, _)| *name == placeholder)
+            .ok_or_else(|| {
+                JamfError::Config(format!("No value for {{{placeholder}}} in {template}"))
+            })?;
+
+        path.push_str(&rest[..start]);
+        path.push_str(&encode_segment(values[index].1));
+        used[index] = true;
+        rest = &rest[end + 1..];
+    }
+
+    if rest.contains('}') {
+        return Err(JamfError::Config(format!(
+            "Unopened placeholder in {template}"
+        )));
+    }
+
+    path.push_str(rest);
+
+    match values.iter().zip(used).find(|(_, used)| !used) {
+        Some(((name, _), _)) => Err(JamfError::Config(format!(
+            "No {{{name}}} placeholder in {template}"
+        ))),
+        None => Ok(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_segment, render};
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render("/JSSResource/computers/id/{id}", [("id", "12")]).unwrap(),
+            "/JSSResource/computers/id/12"
+        );
+        assert_eq!(
+            render(
+                "/JSSResource/patchreports/patchsoftwaretitleid/{id}/version/{software_version}",
+                [("software_version", "1.0/beta"), ("id", "3")]
+            )
+            .unwrap(),
+            "/JSSResource/patchreports/patchsoftwaretitleid/3/version/1.0%2Fbeta"
+        );
+        assert_eq!(
+            render(
+                "/JSSResource/computers/match/{search_query}",
+                [("search_query", "Jane's MacBook")]
+            )
+            .unwrap(),
+            "/JSSResource/computers/match/Jane%27s%20MacBook"
+        );
+        assert_eq!(
+            render("/JSSResource/computers", []).unwrap(),
+            "/JSSResource/computers"
+        );
+    }
+
+    #[test]
+    fn test_placeholders_validated() {
+        assert!(render("/JSSResource/computers/id/{id}", []).is_err());
+        assert!(render("/JSSResource/computers/id/{id}", [("name", "Mac")]).is_err());
+        assert!(render("/JSSResource/computers", [("id", "12")]).is_err());
+        assert!(render("/JSSResource/computers/id/{id", [("id", "12")]).is_err());
+        assert!(render("/JSSResource/computers/id/id}", [("id", "12")]).is_err());
+        assert!(render("/JSSResource/computers}/id/{id}", [("id", "12")]).is_err());
+    }
+
+    #[test]
+    fn test_encode_segment() {
+        assert_eq!(encode_segment("a-b_c.d~e"), "a-b_c.d~e");
+        assert_eq!(encode_segment("a/b?c#d"), "a%2Fb%3Fc%23d");
+        assert_eq!(encode_segment("Büro"), "B%C3%BCro");
+        assert_eq!(encode_segment("*"), "%2A");
+    }
+}
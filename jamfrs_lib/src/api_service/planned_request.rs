@@ -0,0 +1,22 @@
+use super::api_endpoints::RequestBody;
+use super::error::JamfError;
+use reqwest::Method;
+
+/// Stands in for the bearer token in planned requests, which are built without authenticating
+pub const REDACTED_TOKEN: &str = "Bearer <redacted>";
+
+/// Request that would be sent while processing commands, as returned by
+/// `JamfApiService::plan` without authenticating or sending anything
+#[derive(Debug, Clone)]
+pub struct PlannedRequest {
+    /// Position of the request in the order the commands were given, starting at 0
+    pub index: usize,
+    /// Id, name, serial number, etc. the request would be sent for; None for commands without one
+    pub arg: Option<String>,
+    pub method: Method,
+    /// Fully resolved URL, or the reason one couldn't be built
+    pub url: Result<String, JamfError>,
+    /// Headers in the order they're set, with the bearer token redacted
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Option<RequestBody>,
+}
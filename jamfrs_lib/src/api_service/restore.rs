@@ -0,0 +1,353 @@
+use super::api_endpoints::{RecordType, RECORD_TYPES};
+use super::error::JamfError;
+use super::select::Selector;
+use super::xml_json::xml_to_json;
+use quick_xml::events::{BytesText, Event};
+use quick_xml::{Reader, Writer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Classic API record saved by a backup or export, to be created anew on the server
+pub struct SavedRecord {
+    pub path: PathBuf,
+    pub record_type: &'static RecordType,
+    /// Id the record had when it was saved, if the document holds one
+    pub id: Option<String>,
+    pub document: String,
+}
+
+/// Reads the .xml documents at a path, which may be a single document or a directory searched
+/// recursively. The kind of each record is taken from the directory holding it, e.g.
+/// policies/12.xml, as backups and exports lay them out. Records are returned in the order they
+/// should be restored in
+pub fn read_saved_records(path: &Path) -> Result<Vec<SavedRecord>, JamfError> {
+    if !path.exists() {
+        return Err(JamfError::Config(format!(
+            "{} doesn't exist",
+            path.display()
+        )));
+    }
+
+    let mut paths = Vec::new();
+    if path.is_dir() {
+        find_documents(path, &mut paths)?;
+    } else {
+        paths.push(path.to_path_buf());
+    }
+
+    let mut records = Vec::new();
+    for path in paths {
+        let entity = path
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let record_type = RecordType::find(&entity).ok_or_else(|| {
+            JamfError::Config(format!(
+                "Can't tell what kind of record {} is; Expected it in a directory named after its records, e.g. policies/12.xml",
+                path.display()
+            ))
+        })?;
+        let document = fs::read_to_string(&path)
+            .map_err(|err| JamfError::Config(format!("Couldn't read {}: {err}", path.display())))?;
+
+        records.push(SavedRecord {
+            id: record_id(&xml_to_json(&document)?),
+            path,
+            record_type,
+            document,
+        });
+    }
+
+    // Sorting is stable, so records of the same kind keep the order they were found in
+    records.sort_by_key(|record| restore_order(record.record_type));
+
+    Ok(records)
+}
+
+fn restore_order(record_type: &RecordType) -> usize {
+    RECORD_TYPES
+        .iter()
+        .position(|other| std::ptr::eq(other, record_type))
+        .unwrap_or(usize::MAX)
+}
+
+fn find_documents(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), JamfError> {
+    let read_error = |err| JamfError::Config(format!("Couldn't read {}: {err}", dir.display()));
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(read_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()
+        .map_err(read_error)?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            find_documents(&path, paths)?;
+        } else if path.extension().is_some_and(|extension| extension == "xml") {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the id of a record, taken from its general section or its root, whether it was
+/// converted from XML or fetched as JSON
+pub(crate) fn record_id(record: &Value) -> Option<String> {
+    ["general.id", "id"].iter().find_map(|path| {
+        let selector: Selector = path.parse().ok()?;
+        match selector.select(record).first()? {
+            Value::String(id) => Some(id.clone()),
+            Value::Number(id) => Some(id.to_string()),
+            _ => None,
+        }
+    })
+}
+
+/// New ids of restored records, by their kind and the id they had when saved
+#[derive(Debug, Default)]
+pub struct IdMap(HashMap<(&'static str, String), String>);
+
+impl IdMap {
+    pub fn insert(&mut self, record_type: &RecordType, old_id: &str, new_id: &str) {
+        self.0.insert(
+            (record_type.element, old_id.to_string()),
+            new_id.to_string(),
+        );
+    }
+
+    pub fn get(&self, record_type: &RecordType, old_id: &str) -> Option<&str> {
+        self.0
+            .get(&(record_type.element, old_id.to_string()))
+            .map(String::as_str)
+    }
+}
+
+/// Fields of a record's general section that the server fills in itself, which are removed along
+/// with every field starting with one of `SERVER_ASSIGNED_PREFIXES`
+const SERVER_ASSIGNED_FIELDS: [&str; 6] = [
+    "udid",
+    "jamf_version",
+    "management_status",
+    "mdm_capable",
+    "mdm_capable_users",
+    "report_id",
+];
+
+/// Timestamps of a record's general section that the server fills in itself, each of which comes
+/// with _epoch and _utc variants, e.g. last_contact_time_epoch
+const SERVER_ASSIGNED_PREFIXES: [&str; 8] = [
+    "report_date",
+    "last_contact_time",
+    "initial_entry_date",
+    "last_enrolled_date",
+    "last_enrollment",
+    "last_inventory_update",
+    "last_cloud_backup_date",
+    "mdm_profile_expiration",
+];
+
+/// Prepares a saved document to be posted to its create endpoint by removing what the server
+/// assigns itself:
+///
+/// - the id of the record, at its root or in its general section
+/// - the fields of the general section in `SERVER_ASSIGNED_FIELDS`, and the timestamps starting
+///   with one of `SERVER_ASSIGNED_PREFIXES`, e.g. last_contact_time_epoch
+/// - the id of the site the record belongs to, anywhere in it, as sites aren't restored; Its name
+///   is kept, which the server finds the site by
+///
+/// Everything else is kept as saved. Given an id map, references to other records, e.g.
+/// `<package><id>5</id>...</package>` in a policy, are pointed at the new ids of the records
+/// restored before it, while references to records that weren't restored keep their old ids
+pub fn prepare(document: &str, id_map: Option<&IdMap>) -> Result<String, JamfError> {
+    let xml_error = |err: quick_xml::Error| JamfError::Parse(err.to_string());
+
+    let mut reader = Reader::from_str(document);
+    let mut writer = Writer::new(Vec::new());
+    let mut path: Vec<String> = Vec::new();
+    // Depth of the server assigned field being skipped
+    let mut skipping: Option<usize> = None;
+
+    loop {
+        let event = reader.read_event().map_err(xml_error)?;
+        match &event {
+            Event::Eof => break,
+            Event::Start(start) => {
+                path.push(String::from_utf8_lossy(start.name().as_ref()).to_string());
+                if skipping.is_none() && is_server_assigned(&path) {
+                    skipping = Some(path.len());
+                }
+            }
+            Event::Empty(start) => {
+                path.push(String::from_utf8_lossy(start.name().as_ref()).to_string());
+                let server_assigned = is_server_assigned(&path);
+                path.pop();
+
+                if server_assigned {
+                    continue;
+                }
+            }
+            Event::End(_) => {
+                let depth = path.len();
+                path.pop();
+
+                if skipping == Some(depth) {
+                    skipping = None;
+                    continue;
+                }
+            }
+            Event::Text(text) if skipping.is_none() => {
+                let new_id = match id_map {
+                    Some(id_map) => referenced_id(&path, &text.unescape().map_err(xml_error)?)
+                        .and_then(|(record_type, old_id)| id_map.get(record_type, &old_id)),
+                    None => None,
+                };
+
+                if let Some(new_id) = new_id {
+                    writer
+                        .write_event(Event::Text(BytesText::new(new_id)))
+                        .map_err(xml_error)?;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        if skipping.is_none() {
+            writer.write_event(event).map_err(xml_error)?;
+        }
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|err| JamfError::Parse(err.to_string()))
+}
+
+fn is_server_assigned(path: &[String]) -> bool {
+    match path {
+        [.., site, id] if site == "site" && id == "id" => true,
+        [_, general, field] if general == "general" => {
+            SERVER_ASSIGNED_FIELDS.contains(&field.as_str())
+                || SERVER_ASSIGNED_PREFIXES
+                    .iter()
+                    .any(|prefix| field.starts_with(prefix))
+                || field == "id"
+        }
+        _ => is_own_id(path),
+    }
+}
+
+// A record's own id is a child of its root element, or of the general section
+fn is_own_id(path: &[String]) -> bool {
+    match path {
+        [_, id] => id == "id",
+        [_, general, id] => general == "general" && id == "id",
+        _ => false,
+    }
+}
+
+// The id of a record referenced from within another, e.g. policy.scripts.script.id
+fn referenced_id(path: &[String], text: &str) -> Option<(&'static RecordType, String)> {
+    match path {
+        [_, .., element, id] if id == "id" && !is_own_id(path) => {
+            let record_type = RECORD_TYPES
+                .iter()
+                .find(|record_type| record_type.element == element)?;
+            Some((record_type, text.trim().to_string()))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{prepare, read_saved_records, IdMap};
+    use crate::api_service::api_endpoints::RecordType;
+    use std::fs;
+
+    const POLICY: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?><policy><general><id>7</id>\
+        <name>Install Chrome</name><category><id>3</id><name>Browsers</name></category></general>\
+        <package_configuration><packages><size>1</size><package><id>5</id><name>Chrome.pkg</name>\
+        </package></packages></package_configuration></policy>";
+
+    #[test]
+    fn test_prepare_strips_ids() {
+        assert_eq!(
+            prepare(POLICY, None).unwrap(),
+            POLICY.replace("<id>7</id>", "")
+        );
+        assert_eq!(
+            prepare("<category><id>3</id><name>Browsers</name></category>", None).unwrap(),
+            "<category><name>Browsers</name></category>"
+        );
+        assert_eq!(
+            prepare("<category><id/><name>Browsers</name></category>", None).unwrap(),
+            "<category><name>Browsers</name></category>"
+        );
+        assert!(prepare("<policy><general></policy>", None).is_err());
+    }
+
+    #[test]
+    fn test_prepare_strips_server_assigned_fields() {
+        let computer = "<computer><general><id>12</id><name>Jane's Mac</name>\
+            <serial_number>C02XL0GZJGH5</serial_number><udid>55900BDC-347C-58B1-D249-F32244B11D30</udid>\
+            <jamf_version>11.4.1</jamf_version><report_date_epoch>1718200000000</report_date_epoch>\
+            <last_contact_time>2024-06-12 15:30:00</last_contact_time><last_contact_time_utc/>\
+            <site><id>2</id><name>London</name></site></general>\
+            <location><username>jane</username></location></computer>";
+
+        assert_eq!(
+            prepare(computer, None).unwrap(),
+            "<computer><general><name>Jane's Mac</name><serial_number>C02XL0GZJGH5</serial_number>\
+                <site><name>London</name></site></general>\
+                <location><username>jane</username></location></computer>"
+        );
+    }
+
+    #[test]
+    fn test_prepare_rewrites_references() {
+        let mut id_map = IdMap::default();
+        id_map.insert(RecordType::find("packages").unwrap(), "5", "105");
+        id_map.insert(RecordType::find("scripts").unwrap(), "3", "103");
+
+        // The category wasn't restored, and the script with the same id doesn't apply to it
+        assert_eq!(
+            prepare(POLICY, Some(&id_map)).unwrap(),
+            POLICY
+                .replace("<id>7</id>", "")
+                .replace("<id>5</id>", "<id>105</id>")
+        );
+    }
+
+    #[test]
+    fn test_read_saved_records() {
+        let dir = std::env::temp_dir().join(format!("jamfrs-restore-{}", std::process::id()));
+        fs::create_dir_all(dir.join("policies")).unwrap();
+        fs::create_dir_all(dir.join("packages")).unwrap();
+        fs::write(dir.join("policies/7.xml"), POLICY).unwrap();
+        fs::write(
+            dir.join("packages/5.xml"),
+            "<package><id>5</id><name>Chrome.pkg</name></package>",
+        )
+        .unwrap();
+        fs::write(dir.join("packages/notes.txt"), "Not a record").unwrap();
+
+        let records = read_saved_records(&dir).unwrap();
+        let records: Vec<(&str, Option<&str>)> = records
+            .iter()
+            .map(|record| (record.record_type.entity(), record.id.as_deref()))
+            .collect();
+        assert_eq!(
+            records,
+            vec![("packages", Some("5")), ("policies", Some("7"))]
+        );
+
+        fs::create_dir_all(dir.join("unknown")).unwrap();
+        fs::write(dir.join("unknown/1.xml"), "<unknown/>").unwrap();
+        assert!(read_saved_records(&dir).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(read_saved_records(&dir).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// Controls how requests are retried after transient failures such as 429, 502 and 503 responses
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts made for a request, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; Doubled for every retry after that
+    pub base_delay: Duration,
+    /// Upper bound for a single delay, including delays requested by a Retry-After header
+    pub max_delay: Duration,
+    /// Retry DELETE requests as well. A DELETE that timed out may have already been applied,
+    /// so these are only retried when asked for
+    pub retry_deletes: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            retry_deletes: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Returns true if requests with the given method may be retried under this policy
+    pub fn applies_to(&self, method: &Method) -> bool {
+        match *method {
+            Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT => true,
+            Method::DELETE => self.retry_deletes,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the status code indicates a transient failure worth retrying
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// Delay before the given retry attempt (starting at 1). A delay requested by the server is
+    /// used as is, otherwise the delay grows exponentially with jitter to avoid retrying in lockstep
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+
+        // Wait somewhere between half and all of the backoff
+        backoff / 2 + backoff.mul_f64(fastrand::f64() / 2.0)
+    }
+}
+
+/// Reads the delay requested by a Retry-After header. Only the delay-seconds form is supported
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{retry_after, RetryPolicy};
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+    use reqwest::{Method, StatusCode};
+    use std::time::Duration;
+
+    #[test]
+    fn test_idempotent_methods_retried_by_default() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.applies_to(&Method::GET));
+        assert!(policy.applies_to(&Method::PUT));
+        assert!(!policy.applies_to(&Method::POST));
+        assert!(!policy.applies_to(&Method::DELETE));
+
+        let policy = RetryPolicy {
+            retry_deletes: true,
+            ..RetryPolicy::default()
+        };
+
+        assert!(policy.applies_to(&Method::DELETE));
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::TOO_MANY_REQUESTS
+        ));
+        assert!(RetryPolicy::is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::SERVICE_UNAVAILABLE
+        ));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!RetryPolicy::is_retryable_status(
+            StatusCode::INTERNAL_SERVER_ERROR
+        ));
+    }
+
+    #[test]
+    fn test_exponential_delay_with_jitter() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            ..RetryPolicy::default()
+        };
+
+        for (attempt, backoff) in [(1, 100), (2, 200), (3, 400), (4, 800), (5, 1000), (9, 1000)] {
+            let delay = policy.delay(attempt, None);
+            assert!(delay >= Duration::from_millis(backoff / 2));
+            assert!(delay <= Duration::from_millis(backoff));
+        }
+    }
+
+    #[test]
+    fn test_retry_after_honoured_and_capped() {
+        let policy = RetryPolicy {
+            max_delay: Duration::from_secs(10),
+            ..RetryPolicy::default()
+        };
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        let requested = retry_after(&headers);
+        assert_eq!(requested, Some(Duration::from_secs(3)));
+        assert_eq!(policy.delay(1, requested), Duration::from_secs(3));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(
+            policy.delay(1, retry_after(&headers)),
+            Duration::from_secs(10)
+        );
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+}
@@ -0,0 +1,146 @@
+use super::error::JamfError;
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// Dotted path to the values to extract from a response, e.g. computer.general.serial_number.
+/// The root element of a Classic API response may be left out, so general.serial_number works as
+/// well. Numbers index into arrays, while names are looked up in every element of an array, so
+/// computers.name returns the name of every computer in a list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector(Vec<String>);
+
+impl Selector {
+    /// Returns every value the path leads to in the response, in the order they appear. Responses
+    /// converted from XML hold strings in place of numbers and booleans
+    pub fn select<'a>(&self, response: &'a Value) -> Vec<&'a Value> {
+        let selected = self.select_from(response);
+
+        match response {
+            Value::Object(root) if selected.is_empty() && root.len() == 1 => {
+                self.select_from(root.values().next().unwrap())
+            }
+            _ => selected,
+        }
+    }
+
+    fn select_from<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![value];
+
+        for segment in &self.0 {
+            current = current
+                .into_iter()
+                .flat_map(|value| step(value, segment))
+                .collect();
+        }
+
+        current
+    }
+}
+
+fn step<'a>(value: &'a Value, segment: &str) -> Vec<&'a Value> {
+    match value {
+        Value::Object(fields) => fields.get(segment).into_iter().collect(),
+        Value::Array(values) => match segment.parse::<usize>() {
+            Ok(index) => values.get(index).into_iter().collect(),
+            Err(_) => values
+                .iter()
+                .flat_map(|value| step(value, segment))
+                .collect(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.join("."))
+    }
+}
+
+impl FromStr for Selector {
+    type Err = JamfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments: Vec<String> = s.trim().split('.').map(str::to_string).collect();
+
+        if segments.iter().any(String::is_empty) {
+            return Err(JamfError::Config(format!(
+                "Invalid path '{s}'; Expected field names separated by dots, e.g. general.name"
+            )));
+        }
+
+        Ok(Selector(segments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selector;
+    use crate::api_service::xml_json::xml_to_json;
+    use serde_json::{json, Value};
+
+    fn select(path: &str, response: &Value) -> Vec<Value> {
+        path.parse::<Selector>()
+            .unwrap()
+            .select(response)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_select() {
+        let response = json!({"computer": {"general": {"id": 12, "name": "Jane's Mac"}}});
+
+        assert_eq!(
+            select("computer.general.name", &response),
+            vec![json!("Jane's Mac")]
+        );
+        assert_eq!(select("general.name", &response), vec![json!("Jane's Mac")]);
+        assert_eq!(
+            select("general", &response),
+            vec![json!({"id": 12, "name": "Jane's Mac"})]
+        );
+        assert!(select("general.serial_number", &response).is_empty());
+        assert!(select("name", &response).is_empty());
+    }
+
+    #[test]
+    fn test_select_arrays() {
+        let response = json!({"computers": [{"id": 1, "name": "one"}, {"id": 2, "name": "two"}]});
+
+        assert_eq!(
+            select("computers.name", &response),
+            vec![json!("one"), json!("two")]
+        );
+        assert_eq!(select("id", &response), vec![json!(1), json!(2)]);
+        assert_eq!(select("computers.1.id", &response), vec![json!(2)]);
+        assert!(select("computers.2.id", &response).is_empty());
+    }
+
+    #[test]
+    fn test_select_xml() {
+        let xml = "<computers><size>2</size><computer><id>1</id><name>one</name></computer>\
+            <computer><id>2</id><name>two</name></computer></computers>";
+        let json = json!({"computers": [{"id": 1, "name": "one"}, {"id": 2, "name": "two"}]});
+        let xml = xml_to_json(xml).unwrap();
+
+        assert_eq!(select("name", &xml), select("name", &json));
+        assert_eq!(select("computers.0.name", &xml), vec![json!("one")]);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "computer.general.name"
+                .parse::<Selector>()
+                .unwrap()
+                .to_string(),
+            "computer.general.name"
+        );
+        assert!("".parse::<Selector>().is_err());
+        assert!("general..name".parse::<Selector>().is_err());
+        assert!(".name".parse::<Selector>().is_err());
+    }
+}
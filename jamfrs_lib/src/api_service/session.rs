@@ -1,26 +1,44 @@
 mod api_token;
-use api_token::ApiToken;
+use super::api_endpoints::ApiEndpoints;
+use super::error::JamfError;
+use api_token::{ApiToken, OAuthToken};
 use regex::Regex;
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use tokio::sync::RwLock;
 use Result::Err;
 
+// Number of seconds before a token expires at which it will be proactively renewed
+const TOKEN_RENEWAL_WINDOW: u64 = 60;
+
+/// Credentials used to obtain bearer tokens from the Jamf Pro server
+#[derive(Debug)]
+pub enum AuthMode {
+    /// Username and password of a Jamf Pro user account
+    Basic { username: String, password: String },
+    /// Client ID and secret of a Jamf Pro API client
+    ClientCredentials {
+        client_id: String,
+        client_secret: String,
+    },
+}
+
 #[derive(Debug)]
 pub struct Session {
     pub server_address: String,
+    #[allow(dead_code)]
     pub port: u16,
-    pub username: String,
-    pub password: String,
-    pub insecure: bool,
-    pub api_token: Option<ApiToken>,
+    pub auth: AuthMode,
+    pub api_token: RwLock<Option<ApiToken>>,
 }
 
 impl Session {
     pub fn new(
         server_address: String,
         port: Option<u16>,
-        username: String,
-        password: String,
+        auth: AuthMode,
         insecure: bool,
-    ) -> Result<Self, &'static str> {
+    ) -> Result<Self, JamfError> {
         let proto_pattern = Regex::new(r"^https?://").unwrap();
         let port_pattern = Regex::new(r":[0-9]*$").unwrap();
 
@@ -36,7 +54,9 @@ impl Session {
                         port_pattern.captures(&server_address).unwrap()[0].to_string();
                     port_str.remove(0); // remove semi-colon
 
-                    port_str.parse::<u16>().unwrap()
+                    port_str.parse::<u16>().map_err(|err| {
+                        JamfError::Config(format!("Invalid port in server address: {err}"))
+                    })?
                 } else if insecure {
                     8080
                 } else {
@@ -61,7 +81,9 @@ impl Session {
             if !port_pattern.is_match(&addr) {
                 addr.push_str(format!(":{port_val}").as_str());
             } else if port.is_some() {
-                return Err("Error: Don't include a port with the server_address while using the --port flag");
+                return Err(JamfError::Config(String::from(
+                    "Don't include a port with the server_address while using the --port flag",
+                )));
             }
 
             addr
@@ -70,36 +92,164 @@ impl Session {
         Ok(Self {
             server_address: server_address_val,
             port: port_val,
-            username,
-            password,
-            insecure,
-            api_token: None,
+            auth,
+            api_token: RwLock::new(None),
         })
     }
 
-    pub fn create_auth_token(&mut self, token_string: String) -> Result<(), String> {
-        self.api_token = match serde_json::from_str(token_string.as_str()) {
-            Ok(token) => Some(token),
-            Err(err) => {
-                return Err(err.to_string());
+    /// Returns a bearer token for the session, authenticating or renewing the current token if it's
+    /// missing or about to expire. Safe to call from many tasks at once; only one will renew the token.
+    pub async fn bearer_token(&self, client: &Client) -> Result<String, JamfError> {
+        if let Some(token) = self.api_token.read().await.as_ref() {
+            if !token.expires_within(TOKEN_RENEWAL_WINDOW) {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let mut api_token = self.api_token.write().await;
+
+        // Another task may have renewed the token while we were waiting on the lock
+        let renewed = match api_token.as_ref() {
+            Some(token) if !token.expires_within(TOKEN_RENEWAL_WINDOW) => {
+                return Ok(token.token.clone())
             }
+            // API client tokens can't be kept alive, so those are always re-requested
+            Some(token)
+                if !token.expires_within(0) && matches!(self.auth, AuthMode::Basic { .. }) =>
+            {
+                match self.keep_alive(client, &token.token).await {
+                    Ok(renewed) => renewed,
+                    // The token was invalidated server side, or the server doesn't offer
+                    // keep-alive, so a new one is needed either way. Other failures, such as the
+                    // server being unreachable, are returned as authenticating again won't help
+                    Err(JamfError::Auth { status, .. })
+                        if status == StatusCode::UNAUTHORIZED
+                            || status == StatusCode::NOT_FOUND =>
+                    {
+                        self.authenticate(client).await?
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+            _ => self.authenticate(client).await?,
         };
 
-        Ok(())
+        let token = renewed.token.clone();
+        *api_token = Some(renewed);
+
+        Ok(token)
+    }
+
+    /// Re-authenticates after the server rejected `rejected_token`. If another task has already
+    /// replaced the rejected token, the replacement is returned instead of authenticating again.
+    pub async fn renew_rejected_token(
+        &self,
+        client: &Client,
+        rejected_token: &str,
+    ) -> Result<String, JamfError> {
+        let mut api_token = self.api_token.write().await;
+
+        if let Some(token) = api_token.as_ref() {
+            if token.token != rejected_token {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let renewed = self.authenticate(client).await?;
+        let token = renewed.token.clone();
+        *api_token = Some(renewed);
+
+        Ok(token)
+    }
+
+    async fn authenticate(&self, client: &Client) -> Result<ApiToken, JamfError> {
+        match &self.auth {
+            AuthMode::Basic { username, password } => {
+                let res = client
+                    .post(format!(
+                        "{}{}",
+                        &self.server_address,
+                        ApiEndpoints::TokenAuth.usage().url
+                    ))
+                    .basic_auth(username, Some(password))
+                    .send()
+                    .await;
+
+                Session::parse_token_response(res).await
+            }
+            AuthMode::ClientCredentials {
+                client_id,
+                client_secret,
+            } => {
+                let res = client
+                    .post(format!(
+                        "{}{}",
+                        &self.server_address,
+                        ApiEndpoints::OAuthToken.usage().url
+                    ))
+                    .form(&[
+                        ("grant_type", "client_credentials"),
+                        ("client_id", client_id),
+                        ("client_secret", client_secret),
+                    ])
+                    .send()
+                    .await;
+
+                Session::parse_token_response::<OAuthToken>(res)
+                    .await
+                    .map(ApiToken::from)
+            }
+        }
+    }
+
+    async fn keep_alive(&self, client: &Client, token: &str) -> Result<ApiToken, JamfError> {
+        let res = client
+            .post(format!(
+                "{}{}",
+                &self.server_address,
+                ApiEndpoints::TokenKeepAlive.usage().url
+            ))
+            .bearer_auth(token)
+            .send()
+            .await;
+
+        Session::parse_token_response(res).await
+    }
+
+    async fn parse_token_response<T: DeserializeOwned>(
+        res: Result<reqwest::Response, reqwest::Error>,
+    ) -> Result<T, JamfError> {
+        let token_res = res?;
+
+        if token_res.status().is_success() {
+            let token_string = token_res.text().await?;
+            Ok(serde_json::from_str(token_string.as_str())?)
+        } else {
+            Err(JamfError::Auth {
+                status: token_res.status(),
+                reason: format!("{} for {}", token_res.status(), token_res.url().path()),
+            })
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Session;
+    use super::{AuthMode, Session};
+
+    fn basic_auth() -> AuthMode {
+        AuthMode::Basic {
+            username: String::from("test"),
+            password: String::from("test-password"),
+        }
+    }
 
     #[test]
     fn test_jamfcloud() {
         let session = Session::new(
             String::from("test.jamfcloud.com"),
             None,
-            String::from("test"),
-            String::from("test-password"),
+            basic_auth(),
             false,
         )
         .unwrap();
@@ -111,26 +261,13 @@ mod tests {
     #[test]
     fn test_jps_default_port() {
         // Hostname
-        let session = Session::new(
-            String::from("test.jps.com"),
-            None,
-            String::from("test"),
-            String::from("test-password"),
-            false,
-        )
-        .unwrap();
+        let session =
+            Session::new(String::from("test.jps.com"), None, basic_auth(), false).unwrap();
 
         assert!(session.server_address.starts_with("https://"));
         assert_eq!(session.port, 8443);
 
-        let session = Session::new(
-            String::from("192.168.1.1"),
-            None,
-            String::from("test"),
-            String::from("test-password"),
-            false,
-        )
-        .unwrap();
+        let session = Session::new(String::from("192.168.1.1"), None, basic_auth(), false).unwrap();
 
         assert!(session.server_address.starts_with("https://"));
         assert_eq!(session.port, 8443);
@@ -141,8 +278,7 @@ mod tests {
         let session = Session::new(
             String::from("test.jps.com"),
             Some(2022),
-            String::from("test"),
-            String::from("test-password"),
+            basic_auth(),
             false,
         )
         .unwrap();
@@ -150,14 +286,8 @@ mod tests {
         assert!(session.server_address.starts_with("https://"));
         assert_eq!(session.port, 2022);
 
-        let session = Session::new(
-            String::from("192.168.1.1"),
-            Some(2022),
-            String::from("test"),
-            String::from("test-password"),
-            false,
-        )
-        .unwrap();
+        let session =
+            Session::new(String::from("192.168.1.1"), Some(2022), basic_auth(), false).unwrap();
 
         assert!(session.server_address.starts_with("https://"));
         assert_eq!(session.port, 2022);
@@ -165,26 +295,12 @@ mod tests {
 
     #[test]
     fn test_jps_insecure_default_port() {
-        let session = Session::new(
-            String::from("test.jps.com"),
-            None,
-            String::from("test"),
-            String::from("test-password"),
-            true,
-        )
-        .unwrap();
+        let session = Session::new(String::from("test.jps.com"), None, basic_auth(), true).unwrap();
 
         assert!(session.server_address.starts_with("http://"));
         assert_eq!(session.port, 8080);
 
-        let session = Session::new(
-            String::from("192.168.1.1"),
-            None,
-            String::from("test"),
-            String::from("test-password"),
-            true,
-        )
-        .unwrap();
+        let session = Session::new(String::from("192.168.1.1"), None, basic_auth(), true).unwrap();
 
         assert!(session.server_address.starts_with("http://"));
         assert_eq!(session.port, 8080);
@@ -192,26 +308,14 @@ mod tests {
 
     #[test]
     fn test_jps_inline_port() {
-        let session = Session::new(
-            String::from("test.jps.com:2022"),
-            None,
-            String::from("test"),
-            String::from("test-password"),
-            false,
-        )
-        .unwrap();
+        let session =
+            Session::new(String::from("test.jps.com:2022"), None, basic_auth(), false).unwrap();
 
         assert!(session.server_address.starts_with("https://"));
         assert_eq!(session.port, 2022);
 
-        let session = Session::new(
-            String::from("192.168.1.1:2022"),
-            None,
-            String::from("test"),
-            String::from("test-password"),
-            false,
-        )
-        .unwrap();
+        let session =
+            Session::new(String::from("192.168.1.1:2022"), None, basic_auth(), false).unwrap();
 
         assert!(session.server_address.starts_with("https://"));
         assert_eq!(session.port, 2022);
@@ -222,8 +326,7 @@ mod tests {
         let session = Session::new(
             String::from("test.jps.com:2022"),
             Some(2022),
-            String::from("test"),
-            String::from("test-password"),
+            basic_auth(),
             false,
         );
 
@@ -235,8 +338,7 @@ mod tests {
         let session = Session::new(
             String::from("https://test.jps.com"),
             None,
-            String::from("test"),
-            String::from("test-password"),
+            basic_auth(),
             false,
         )
         .unwrap();
@@ -246,12 +348,28 @@ mod tests {
         let session = Session::new(
             String::from("http://test.jps.com"),
             None,
-            String::from("test"),
-            String::from("test-password"),
+            basic_auth(),
             true,
         )
         .unwrap();
 
         assert!(session.server_address.starts_with("http://"));
     }
+
+    #[test]
+    fn test_client_credentials() {
+        let session = Session::new(
+            String::from("test.jamfcloud.com"),
+            None,
+            AuthMode::ClientCredentials {
+                client_id: String::from("test-client"),
+                client_secret: String::from("test-secret"),
+            },
+            false,
+        )
+        .unwrap();
+
+        assert!(matches!(session.auth, AuthMode::ClientCredentials { .. }));
+        assert_eq!(session.port, 443);
+    }
 }
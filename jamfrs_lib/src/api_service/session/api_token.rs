@@ -1,7 +1,107 @@
 use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiToken {
     pub token: String,
     pub expires: u64,
 }
+
+/// Token response returned by the API client credentials flow
+#[derive(Debug, Deserialize)]
+pub struct OAuthToken {
+    pub access_token: String,
+    pub expires_in: u64,
+}
+
+impl From<OAuthToken> for ApiToken {
+    fn from(oauth_token: OAuthToken) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        ApiToken {
+            token: oauth_token.access_token,
+            expires: now + oauth_token.expires_in,
+        }
+    }
+}
+
+impl ApiToken {
+    /// Returns true if the token has expired or will expire within the given number of seconds
+    pub fn expires_within(&self, seconds: u64) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        now + seconds >= self.expires_at()
+    }
+
+    fn expires_at(&self) -> u64 {
+        // Jamf Pro reports token expiration as epoch milliseconds
+        if self.expires > 10_000_000_000 {
+            self.expires / 1000
+        } else {
+            self.expires
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiToken, OAuthToken};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    fn token(expires: u64) -> ApiToken {
+        ApiToken {
+            token: String::from("test-token"),
+            expires,
+        }
+    }
+
+    #[test]
+    fn test_expires_within() {
+        let expiring = token(now() + 30);
+        assert!(expiring.expires_within(60));
+        assert!(!expiring.expires_within(0));
+
+        let fresh = token(now() + 120);
+        assert!(!fresh.expires_within(60));
+        assert!(fresh.expires_within(180));
+
+        assert!(token(now() - 1).expires_within(0));
+    }
+
+    #[test]
+    fn test_expires_in_milliseconds() {
+        let expires = now() + 120;
+
+        assert_eq!(token(expires * 1000).expires_at(), expires);
+        assert_eq!(token(expires).expires_at(), expires);
+        assert!(!token(expires * 1000).expires_within(60));
+        assert!(token((now() + 30) * 1000).expires_within(60));
+    }
+
+    #[test]
+    fn test_from_oauth_token() {
+        let before = now();
+        let token = ApiToken::from(OAuthToken {
+            access_token: String::from("oauth-token"),
+            expires_in: 1199,
+        });
+
+        assert_eq!(token.token, "oauth-token");
+        assert!(token.expires >= before + 1199 && token.expires <= now() + 1199);
+        assert!(!token.expires_within(60));
+        assert!(token.expires_within(1200));
+    }
+}
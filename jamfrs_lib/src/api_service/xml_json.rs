@@ -0,0 +1,229 @@
+use super::error::JamfError;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde_json::{Map, Value};
+
+#[derive(Default)]
+struct Node {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+    text: String,
+}
+
+/// Converts an XML document into the JSON the Classic API would have responded with, for when
+/// it ignores a request for JSON. The root element becomes the single key of the document, e.g.
+/// `{"computer": {...}}`, and:
+/// - Elements holding only text become strings; Values aren't converted to numbers or booleans, so
+///   serial numbers such as 0123 survive
+/// - Empty elements become ""
+/// - Repeated elements become arrays
+/// - Lists wrapped with a `<size>` element, e.g. `<computers><size>2</size><computer/>...`, become
+///   arrays of their records, even when empty or holding a single record
+/// - Attributes become keys prefixed with @, and text alongside child elements is kept under #text
+pub fn xml_to_json(xml: &str) -> Result<Value, JamfError> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Node> = Vec::new();
+    let mut root = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(start)) => stack.push(node(&start)?),
+            Ok(Event::Empty(start)) => {
+                let node = node(&start)?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => root = Some(node),
+                }
+            }
+            Ok(Event::End(_)) => {
+                let node = stack
+                    .pop()
+                    .ok_or_else(|| JamfError::Parse(String::from("Unexpected closing tag")))?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => root = Some(node),
+                }
+            }
+            Ok(Event::Text(text)) => {
+                if let Some(node) = stack.last_mut() {
+                    node.text.push_str(&text.unescape().map_err(parse_error)?);
+                }
+            }
+            Ok(Event::CData(data)) => {
+                if let Some(node) = stack.last_mut() {
+                    node.text
+                        .push_str(&String::from_utf8_lossy(&data.into_inner()));
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(err) => return Err(parse_error(err)),
+        }
+    }
+
+    match root {
+        Some(root) if stack.is_empty() => {
+            let mut document = Map::new();
+            let name = root.name.clone();
+            document.insert(name, to_value(root));
+            Ok(Value::Object(document))
+        }
+        _ => Err(JamfError::Parse(String::from(
+            "XML document has no complete root element",
+        ))),
+    }
+}
+
+fn parse_error(err: impl std::fmt::Display) -> JamfError {
+    JamfError::Parse(err.to_string())
+}
+
+fn node(start: &BytesStart) -> Result<Node, JamfError> {
+    let mut attributes = Vec::new();
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(parse_error)?;
+        attributes.push((
+            String::from_utf8_lossy(attribute.key.as_ref()).to_string(),
+            attribute.unescape_value().map_err(parse_error)?.to_string(),
+        ));
+    }
+
+    Ok(Node {
+        name: String::from_utf8_lossy(start.name().as_ref()).to_string(),
+        attributes,
+        ..Node::default()
+    })
+}
+
+/// Returns true if the node is a list of records with the same name alongside a `<size>` element
+/// counting them
+fn is_size_wrapped(node: &Node) -> bool {
+    let Some(size) = node.children.iter().find(|child| child.name == "size") else {
+        return false;
+    };
+    let mut records = node.children.iter().filter(|child| child.name != "size");
+    let first = records.next();
+
+    node.attributes.is_empty()
+        && size.text.trim().parse() == Ok(node.children.len() - 1)
+        && records.all(|record| first.is_some_and(|first| first.name == record.name))
+}
+
+fn to_value(node: Node) -> Value {
+    if is_size_wrapped(&node) {
+        return Value::Array(
+            node.children
+                .into_iter()
+                .filter(|child| child.name != "size")
+                .map(to_value)
+                .collect(),
+        );
+    }
+
+    if node.children.is_empty() && node.attributes.is_empty() {
+        return Value::String(node.text.trim().to_string());
+    }
+
+    let mut object = Map::new();
+    for (key, value) in node.attributes {
+        object.insert(format!("@{key}"), Value::String(value));
+    }
+
+    for child in node.children {
+        let name = child.name.clone();
+        let value = to_value(child);
+
+        match object.get_mut(&name) {
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                object.insert(name, value);
+            }
+        }
+    }
+
+    let text = node.text.trim();
+    if !text.is_empty() {
+        object.insert(String::from("#text"), Value::String(text.to_string()));
+    }
+
+    Value::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::xml_to_json;
+    use serde_json::json;
+
+    #[test]
+    fn test_elements() {
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?><computer><general><id>12</id>\
+            <name>Jane's &amp; Co</name><serial_number>0123</serial_number><asset_tag/>\
+            <notes></notes></general></computer>";
+
+        assert_eq!(
+            xml_to_json(xml).unwrap(),
+            json!({"computer": {"general": {
+                "id": "12",
+                "name": "Jane's & Co",
+                "serial_number": "0123",
+                "asset_tag": "",
+                "notes": ""
+            }}})
+        );
+    }
+
+    #[test]
+    fn test_repeated_elements_become_arrays() {
+        let xml = "<policy><scope><building>A</building><building>B</building>\
+            <building>C</building></scope></policy>";
+
+        assert_eq!(
+            xml_to_json(xml).unwrap(),
+            json!({"policy": {"scope": {"building": ["A", "B", "C"]}}})
+        );
+    }
+
+    #[test]
+    fn test_size_wrapped_lists() {
+        let xml = "<computer_group><computers><size>1</size><computer><id>1</id></computer>\
+            </computers><sites><size>0</size></sites></computer_group>";
+
+        assert_eq!(
+            xml_to_json(xml).unwrap(),
+            json!({"computer_group": {"computers": [{"id": "1"}], "sites": []}})
+        );
+
+        // A size that doesn't match the records isn't a list wrapper
+        let xml = "<disk><size>500</size><model>SSD</model></disk>";
+        assert_eq!(
+            xml_to_json(xml).unwrap(),
+            json!({"disk": {"size": "500", "model": "SSD"}})
+        );
+    }
+
+    #[test]
+    fn test_attributes_and_cdata() {
+        let xml = "<script><name lang=\"en\">Clean</name><contents><![CDATA[echo \"<hi>\"]]>\
+            </contents></script>";
+
+        assert_eq!(
+            xml_to_json(xml).unwrap(),
+            json!({"script": {
+                "name": {"@lang": "en", "#text": "Clean"},
+                "contents": "echo \"<hi>\""
+            }})
+        );
+    }
+
+    #[test]
+    fn test_invalid_documents() {
+        assert!(xml_to_json("").is_err());
+        assert!(xml_to_json("<computer><general></computer>").is_err());
+        assert!(xml_to_json("not xml").is_err());
+    }
+}
@@ -1,56 +1,105 @@
 pub mod api_service {
     pub mod api_endpoints;
+    pub mod command_result;
+    pub mod error;
+    pub mod export;
+    pub mod filter;
+    pub mod models;
+    pub mod pagination;
+    pub mod path_template;
+    pub mod planned_request;
+    pub mod restore;
+    pub mod retry;
+    pub mod select;
+    pub mod xml_json;
 
     mod session;
-    use api_endpoints::{ApiEndpoints, Args, CommandDetails};
-    use reqwest::Client;
+    use api_endpoints::{
+        ApiEndpointDetails, Args, CommandDetails, Identifier, RecordType, RequestBody,
+    };
+    pub use command_result::CommandResult;
+    pub use error::JamfError;
+    use export::ListedRecord;
+    use futures_util::stream::{self, Stream, StreamExt};
+    use models::{Computer, Record};
+    use pagination::{Page, PageQuery};
+    use percent_encoding::percent_decode_str;
+    pub use planned_request::PlannedRequest;
+    use reqwest::header::CONTENT_TYPE;
     use reqwest::Method;
+    use reqwest::{Client, StatusCode};
+    use retry::RetryPolicy;
+    use serde_json::Value;
+    pub use session::AuthMode;
     use session::Session;
-    use std::time::{SystemTime, UNIX_EPOCH};
-    use tokio::sync::mpsc::{channel, Receiver};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::mpsc::{channel, Receiver, Sender};
+    use tokio::sync::Semaphore;
+    use tokio::time::{sleep, Instant};
+    use xml_json::xml_to_json;
+
+    pub const DEFAULT_MAX_CONCURRENCY: usize = 10;
 
     pub struct JamfApiService {
         client: Client,
-        jps_session: Session,
+        jps_session: Arc<Session>,
         json: bool,
+        max_concurrency: usize,
+        retry_policy: RetryPolicy,
         url_builder: Option<UrlBuilder>,
+        backup_dir: Option<PathBuf>,
     }
 
     impl JamfApiService {
         pub fn new(
             server_address: String,
             port: Option<u16>,
-            username: String,
-            password: String,
+            auth: AuthMode,
             insecure: bool,
             json: bool,
-        ) -> Result<Self, String> {
-            let client = match Client::builder()
+        ) -> Result<Self, JamfError> {
+            let client = Client::builder()
                 .danger_accept_invalid_certs(insecure)
                 .build()
-            {
-                Ok(client) => client,
-                Err(err) => return Err(err.to_string()),
-            };
+                .map_err(|err| JamfError::Config(err.to_string()))?;
 
-            let jps_session = match Session::new(server_address, port, username, password, insecure)
-            {
-                Ok(session) => session,
-                Err(err) => return Err(err.to_string()),
-            };
+            let jps_session = Session::new(server_address, port, auth, insecure)?;
 
             Ok(Self {
                 client,
-                jps_session,
+                jps_session: Arc::new(jps_session),
                 json,
+                max_concurrency: DEFAULT_MAX_CONCURRENCY,
+                retry_policy: RetryPolicy::default(),
                 url_builder: None,
+                backup_dir: None,
             })
         }
 
+        /// Sets the maximum number of requests that may be in flight at once while processing commands
+        pub fn set_max_concurrency(&mut self, max_concurrency: usize) {
+            self.max_concurrency = max_concurrency.max(1);
+        }
+
+        /// Sets how requests are retried after transient failures
+        pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+            self.retry_policy = retry_policy;
+        }
+
+        /// Sets a directory to save each record to before it's deleted, as
+        /// `<dir>/<entity>/<id>.xml`, or .json for the Jamf Pro API. Records that can't be saved
+        /// aren't deleted
+        pub fn set_backup_dir(&mut self, backup_dir: Option<PathBuf>) {
+            self.backup_dir = backup_dir;
+        }
+
         pub fn set_commands(
             &mut self,
             commands: CommandDetails,
-        ) -> Result<&CommandDetails, String> {
+        ) -> Result<&CommandDetails, JamfError> {
             self.url_builder = Some(UrlBuilder::new(
                 self.jps_session.server_address.clone(),
                 commands,
@@ -60,106 +109,662 @@ pub mod api_service {
             Ok(api_details_ref)
         }
 
+        /// Address requests are sent to, including the scheme and port
+        pub fn server_address(&self) -> &str {
+            &self.jps_session.server_address
+        }
+
         pub fn number_of_commands(&self) -> u32 {
             match self.url_builder {
                 None => 0,
                 Some(ref url_builder) => match &url_builder.api_details.args {
                     Args::None => 1,
                     Args::Strings(_) => 1,
+                    Args::Pages(_) => 1,
                     Args::Ids(ids) => ids.len() as u32,
+                    Args::Lookup(_, values) => values.len() as u32,
                 },
             }
         }
 
-        pub async fn process_commands(&mut self) -> Receiver<Result<String, String>> {
-            // TODO: Reimplement such that tasks can re-authenticate as necessary
-            if !self.token_is_valid() {
-                self.authenticate()
-                    .await
-                    .expect("Failed to authenticate with server");
-            }
+        /// Sends the requests for the commands that were set, up to the max concurrency at once.
+        /// Fails if the session can't authenticate, otherwise there's a result for every request
+        pub async fn process_commands(&mut self) -> Result<Receiver<CommandResult>, JamfError> {
+            let url_builder = self.url_builder.clone().unwrap();
+            let accept_type = self.accept_type(&url_builder.api_details.endpoint);
+            let method = url_builder.api_details.endpoint.method.clone();
+            let (tx, rx) = channel(self.max_concurrency);
 
-            let accept_type = format!("application/{}", if self.json { "json" } else { "xml" });
-            let (tx, rx) = channel(self.number_of_commands() as usize);
-
-            while let Some(url) = self.url_builder.as_mut().unwrap().next() {
-                let mut res_builder = match self
-                    .url_builder
-                    .as_ref()
-                    .unwrap()
-                    .api_details
-                    .endpoint
-                    .method
-                {
-                    Method::GET => self.client.get(url),
-                    Method::POST => self.client.post(url),
-                    Method::PUT => self.client.put(url),
-                    Method::DELETE => self.client.delete(url),
-                    _ => panic!("Invalid HTTP method provided"),
-                };
+            // Authenticate up front so bad credentials fail once rather than once per request.
+            // Spawned tasks renew the shared token as necessary from here on
+            self.jps_session.bearer_token(&self.client).await?;
 
-                res_builder = res_builder
-                    .bearer_auth(&self.jps_session.api_token.as_ref().unwrap().token)
-                    .header("accept", &accept_type);
+            if let Args::Pages(query) = &url_builder.api_details.args {
+                let base_url = format!(
+                    "{}{}",
+                    self.jps_session.server_address, url_builder.api_details.endpoint.url
+                );
+                let (record_tx, mut record_rx) = channel(self.max_concurrency);
+
+                tokio::spawn(JamfApiService::page_records(
+                    self.request_context(),
+                    base_url.clone(),
+                    query.clone(),
+                    self.max_concurrency,
+                    record_tx,
+                ));
 
-                let tx_clone = tx.clone();
+                // Every record gets a result of its own, timed from when the listing started
                 tokio::spawn(async move {
-                    match res_builder.send().await {
-                        Ok(res) => {
-                            if res.status().is_success() {
-                                tx_clone.send(Ok(res.text().await.unwrap())).await
-                            } else {
-                                tx_clone
-                                    .send(Err(format!("{} for {}", res.status(), res.url().path())))
-                                    .await
-                            }
+                    let start = Instant::now();
+                    let mut index = 0;
+
+                    while let Some(record) = record_rx.recv().await {
+                        let result = CommandResult {
+                            index,
+                            arg: None,
+                            method: method.clone(),
+                            url: base_url.clone(),
+                            status: match &record {
+                                Ok(_) => Some(StatusCode::OK),
+                                Err(err) => err.status(),
+                            },
+                            elapsed: start.elapsed(),
+                            body: record.map(|record| record.to_string()),
+                        };
+
+                        if tx.send(result).await.is_err() {
+                            break;
                         }
-                        Err(err) => tx_clone.send(Err(err.to_string())).await,
+                        index += 1;
                     }
                 });
+
+                return Ok(rx);
             }
 
-            rx
+            let lookup = match url_builder.api_details.args {
+                Args::Lookup(identifier, _) => Some(identifier),
+                _ => None,
+            };
+            let body = url_builder.api_details.body.clone();
+            let is_jamf_pro_api = url_builder.api_details.endpoint.is_jamf_pro_api();
+            let backup_dir = match &self.backup_dir {
+                Some(dir) if method == Method::DELETE => {
+                    Some(dir.join(url_builder.api_details.endpoint.entity()))
+                }
+                _ => None,
+            };
+            let semaphore = Arc::new(Semaphore::new(self.max_concurrency));
+            let request_context = self.request_context();
+
+            // Requests are spawned from a separate task so results can be streamed back through
+            // the receiver while the remaining requests wait for a free slot
+            tokio::spawn(async move {
+                for (index, (arg, url)) in url_builder.enumerate() {
+                    let url = match url {
+                        Ok(url) => url,
+                        Err(err) => {
+                            let result = CommandResult {
+                                index,
+                                arg,
+                                method: method.clone(),
+                                url: String::new(),
+                                status: None,
+                                elapsed: Duration::ZERO,
+                                body: Err(err),
+                            };
+                            let _ = tx.send(result).await;
+                            continue;
+                        }
+                    };
+
+                    let permit = Arc::clone(&semaphore).acquire_owned().await.unwrap();
+                    let request_context = request_context.clone();
+                    let method = method.clone();
+                    let body = body.clone();
+                    let backup_dir = backup_dir.clone();
+
+                    let tx_clone = tx.clone();
+                    tokio::spawn(async move {
+                        let not_found = |err| match lookup {
+                            Some(identifier) => JamfApiService::lookup_not_found(err, identifier),
+                            None => err,
+                        };
+                        let start = Instant::now();
+
+                        let backed_up = match &backup_dir {
+                            Some(dir) => {
+                                let name = arg.clone().unwrap_or_else(|| index.to_string());
+                                request_context
+                                    .back_up(&url, dir, &name, is_jamf_pro_api)
+                                    .await
+                            }
+                            None => Ok(()),
+                        };
+                        let res = match backed_up {
+                            Ok(()) => {
+                                request_context
+                                    .send(method.clone(), url.clone(), accept_type, body.as_ref())
+                                    .await
+                            }
+                            Err(err @ JamfError::Backup { .. }) => Err(err),
+                            Err(err) => Err(JamfError::Backup {
+                                status: err.status(),
+                                reason: not_found(err).to_string(),
+                            }),
+                        };
+                        let elapsed = start.elapsed();
+
+                        let (status, body) = match res {
+                            Ok((status, body)) => (Some(status), Ok(body)),
+                            Err(err) => (err.status(), Err(not_found(err))),
+                        };
+
+                        let result = CommandResult {
+                            index,
+                            arg,
+                            method,
+                            url,
+                            status,
+                            elapsed,
+                            body,
+                        };
+
+                        // Hold onto the permit until the result has been received so a slow
+                        // consumer applies back pressure to the requests being sent
+                        let _ = tx_clone.send(result).await;
+                        drop(permit);
+                    });
+                }
+            });
+
+            Ok(rx)
         }
 
-        async fn authenticate(&mut self) -> Result<(), String> {
-            let res = self
-                .client
-                .post(format!(
+        /// Returns the requests processing the commands that were set would send, without
+        /// authenticating or sending anything. Only the first page of a Jamf Pro API list is
+        /// planned, as the number of pages isn't known until it has been fetched. Deletes are
+        /// preceded by the request backing the record up when a backup directory is set
+        pub fn plan(&self) -> Vec<PlannedRequest> {
+            let Some(url_builder) = self.url_builder.clone() else {
+                return Vec::new();
+            };
+            let method = url_builder.api_details.endpoint.method.clone();
+            let accept_type = self.accept_type(&url_builder.api_details.endpoint);
+
+            if let Args::Pages(query) = &url_builder.api_details.args {
+                let base_url = format!(
                     "{}{}",
-                    &self.jps_session.server_address,
-                    ApiEndpoints::TokenAuth.usage().url
+                    self.jps_session.server_address, url_builder.api_details.endpoint.url
+                );
+
+                return vec![PlannedRequest {
+                    index: 0,
+                    arg: None,
+                    method,
+                    url: query.page_url(&base_url, 0),
+                    headers: planned_headers(accept_type, None),
+                    body: None,
+                }];
+            }
+
+            let body = url_builder.api_details.body.clone();
+            let backup_format = match &self.backup_dir {
+                Some(_) if method == Method::DELETE => Some(backup_format(
+                    url_builder.api_details.endpoint.is_jamf_pro_api(),
+                )),
+                _ => None,
+            };
+            let mut plan = Vec::new();
+
+            for (index, (arg, url)) in url_builder.enumerate() {
+                if let Some((backup_accept_type, _)) = backup_format {
+                    plan.push(PlannedRequest {
+                        index,
+                        arg: arg.clone(),
+                        method: Method::GET,
+                        url: url.clone(),
+                        headers: planned_headers(backup_accept_type, None),
+                        body: None,
+                    });
+                }
+
+                plan.push(PlannedRequest {
+                    index,
+                    arg,
+                    method: method.clone(),
+                    url,
+                    headers: planned_headers(accept_type, body.as_ref()),
+                    body: body.clone(),
+                });
+            }
+
+            plan
+        }
+
+        /// Streams every record from a Jamf Pro API list endpoint, fetching the remaining pages in
+        /// parallel (up to the max concurrency) once the total number of records is known
+        pub fn stream_records(
+            &self,
+            endpoint: &ApiEndpointDetails,
+            query: PageQuery,
+        ) -> impl Stream<Item = Result<Value, JamfError>> {
+            let (tx, mut rx) = channel(self.max_concurrency);
+
+            tokio::spawn(JamfApiService::page_records(
+                self.request_context(),
+                format!("{}{}", self.jps_session.server_address, endpoint.url),
+                query,
+                self.max_concurrency,
+                tx,
+            ));
+
+            stream::poll_fn(move |cx| rx.poll_recv(cx))
+        }
+
+        /// Fetches a single Classic API record by id, e.g. `service.get_record::<Policy>(3)`
+        pub async fn get_record<T: Record>(&self, id: u32) -> Result<T, JamfError> {
+            let url = format!(
+                "{}{}",
+                self.jps_session.server_address,
+                path_template::render(T::SHOW.usage().url, [("id", id.to_string().as_str())])?
+            );
+            let accept_type = if self.json {
+                "application/json"
+            } else {
+                "application/xml"
+            };
+
+            let (_, body) = self
+                .request_context()
+                .send(Method::GET, url, accept_type, None)
+                .await?;
+
+            if self.json {
+                models::from_json(&body)
+            } else {
+                models::from_xml(&body)
+            }
+        }
+
+        pub async fn get_computer(&self, id: u32) -> Result<Computer, JamfError> {
+            self.get_record(id).await
+        }
+
+        /// Creates a Classic API record from an XML document, such as one prepared with
+        /// `restore::prepare`, returning the id the server assigned it. The record was created
+        /// even if the id is None, which happens when the response doesn't hold one
+        pub async fn create_record(
+            &self,
+            record_type: &RecordType,
+            document: String,
+        ) -> Result<Option<String>, JamfError> {
+            let url = self.create_url(record_type)?;
+
+            let (_, body) = self
+                .request_context()
+                .send(
+                    Method::POST,
+                    url,
+                    "application/xml",
+                    Some(&RequestBody::new(document)),
+                )
+                .await?;
+
+            // The server responds with the new id alone, e.g. <policy><id>12</id></policy>
+            Ok(xml_to_json(&body).ok().and_then(|created| {
+                created
+                    .as_object()?
+                    .values()
+                    .next()?
+                    .get("id")?
+                    .as_str()
+                    .map(str::to_string)
+            }))
+        }
+
+        /// Request `create_record` would send, planned the same way as [`Self::plan`]
+        pub fn plan_create_record(
+            &self,
+            index: usize,
+            record_type: &RecordType,
+            document: String,
+        ) -> PlannedRequest {
+            let body = RequestBody::new(document);
+
+            PlannedRequest {
+                index,
+                arg: None,
+                method: Method::POST,
+                url: self.create_url(record_type),
+                headers: planned_headers("application/xml", Some(&body)),
+                body: Some(body),
+            }
+        }
+
+        /// Lists the id and name of every Classic API record of a type
+        pub async fn list_records(
+            &self,
+            record_type: &RecordType,
+        ) -> Result<Vec<ListedRecord>, JamfError> {
+            let url = format!(
+                "{}{}",
+                self.jps_session.server_address,
+                record_type.list.usage().url
+            );
+
+            let (_, body) = self
+                .request_context()
+                .send(Method::GET, url, "application/xml", None)
+                .await?;
+
+            export::listed_records(&body)
+        }
+
+        /// Request `list_records` would send, planned the same way as [`Self::plan`]
+        pub fn plan_list_records(&self, index: usize, record_type: &RecordType) -> PlannedRequest {
+            PlannedRequest {
+                index,
+                arg: None,
+                method: Method::GET,
+                url: Ok(format!(
+                    "{}{}",
+                    self.jps_session.server_address,
+                    record_type.list.usage().url
+                )),
+                headers: planned_headers("application/xml", None),
+                body: None,
+            }
+        }
+
+        /// Fetches the version of Jamf Pro the server is running, e.g. 11.4.1-t1712591696
+        pub async fn jamf_pro_version(&self) -> Result<String, JamfError> {
+            let url = format!(
+                "{}/api/v1/jamf-pro-version",
+                self.jps_session.server_address
+            );
+
+            let (_, body) = self
+                .request_context()
+                .send(Method::GET, url, "application/json", None)
+                .await?;
+
+            serde_json::from_str::<Value>(&body)
+                .map_err(|err| JamfError::Parse(err.to_string()))?
+                .get("version")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| JamfError::Parse(format!("No version in response: {body}")))
+        }
+
+        /// URL records of a type are posted to by `create_record`
+        pub fn create_url(&self, record_type: &RecordType) -> Result<String, JamfError> {
+            let endpoint = record_type.create.as_ref().ok_or_else(|| {
+                JamfError::Config(format!(
+                    "Records in {} can't be created from a document",
+                    record_type.entity()
                 ))
-                .basic_auth(&self.jps_session.username, Some(&self.jps_session.password))
-                .send()
-                .await;
-
-            match res {
-                Ok(token_res) => {
-                    if token_res.status().is_success() {
-                        self.jps_session
-                            .create_auth_token(token_res.text().await.unwrap())
-                    } else {
-                        Err(token_res.status().to_string())
+            })?;
+
+            Ok(format!(
+                "{}{}",
+                self.jps_session.server_address,
+                endpoint.usage().url
+            ))
+        }
+
+        async fn page_records(
+            request_context: RequestContext,
+            base_url: String,
+            query: PageQuery,
+            max_concurrency: usize,
+            tx: Sender<Result<Value, JamfError>>,
+        ) {
+            let fetch_page = |page: usize| {
+                let request_context = &request_context;
+                let url = query.page_url(&base_url, page);
+
+                async move {
+                    let (_, body) = request_context
+                        .send(Method::GET, url?, "application/json", None)
+                        .await?;
+
+                    Ok::<Page, JamfError>(serde_json::from_str(&body)?)
+                }
+            };
+
+            // The first page tells us how many records there are, and so how many pages to fetch
+            let first_page = match fetch_page(0).await {
+                Ok(page) => page,
+                Err(err) => {
+                    let _ = tx.send(Err(err)).await;
+                    return;
+                }
+            };
+
+            let total = query.limit.map_or(first_page.total_count, |limit| {
+                limit.min(first_page.total_count)
+            });
+            let mut pages = stream::iter(1..query.page_count(total))
+                .map(fetch_page)
+                .buffered(max_concurrency);
+            let mut sent = 0;
+            let mut page = Ok(first_page);
+
+            loop {
+                match page {
+                    Ok(page) => {
+                        for record in page.results {
+                            if sent == total || tx.send(Ok(record)).await.is_err() {
+                                return;
+                            }
+                            sent += 1;
+                        }
+                    }
+                    Err(err) => {
+                        if tx.send(Err(err)).await.is_err() {
+                            return;
+                        }
                     }
                 }
-                Err(err) => Err(err.to_string()),
+
+                match pages.next().await {
+                    Some(next_page) => page = next_page,
+                    None => return,
+                }
             }
         }
 
-        fn token_is_valid(&self) -> bool {
-            return if self.jps_session.api_token.is_some() {
-                SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs()
-                    .le(&self.jps_session.api_token.as_ref().unwrap().expires)
+        /// Turns a 404 for a record looked up by name, serial number, etc. into an error naming the
+        /// missing record
+        fn lookup_not_found(err: JamfError, identifier: Identifier) -> JamfError {
+            match err {
+                JamfError::Http { status, url } if status == StatusCode::NOT_FOUND => {
+                    match url.rsplit_once(&format!("/{}/", identifier.path_segment())) {
+                        Some((path, value)) => JamfError::NotFound(format!(
+                            "No record {} in {}",
+                            identifier.describe(&percent_decode_str(value).decode_utf8_lossy()),
+                            path.rsplit('/').next().unwrap_or(path)
+                        )),
+                        None => JamfError::Http { status, url },
+                    }
+                }
+                err => err,
+            }
+        }
+
+        /// Returns the accept type to request for an endpoint. The Jamf Pro API only speaks JSON,
+        /// while some Classic API endpoints are requested as XML regardless
+        fn accept_type(&self, endpoint: &ApiEndpointDetails) -> &'static str {
+            if (self.json && !endpoint.truncates_json()) || endpoint.is_jamf_pro_api() {
+                "application/json"
             } else {
-                false
-            };
+                "application/xml"
+            }
+        }
+
+        fn request_context(&self) -> RequestContext {
+            RequestContext {
+                client: self.client.clone(),
+                jps_session: Arc::clone(&self.jps_session),
+                retry_policy: self.retry_policy.clone(),
+            }
         }
     }
 
+    /// Accept type and file extension records are backed up with; Classic API records are saved as
+    /// XML so they can be posted back as they are
+    fn backup_format(is_jamf_pro_api: bool) -> (&'static str, &'static str) {
+        if is_jamf_pro_api {
+            ("application/json", "json")
+        } else {
+            ("application/xml", "xml")
+        }
+    }
+
+    /// Headers `RequestContext::send` sets, with the bearer token redacted
+    fn planned_headers(
+        accept_type: &str,
+        body: Option<&RequestBody>,
+    ) -> Vec<(&'static str, String)> {
+        let mut headers = vec![
+            (
+                "Authorization",
+                String::from(planned_request::REDACTED_TOKEN),
+            ),
+            ("Accept", accept_type.to_string()),
+        ];
+
+        if let Some(body) = body {
+            headers.push(("Content-Type", body.content_type.to_string()));
+        }
+
+        headers
+    }
+
+    /// Name of the file a record is backed up to, after its id so records looked up by name,
+    /// serial number, etc. are saved the same way as those deleted by id
+    fn backup_file_name(record: &str, name: &str, extension: &str) -> String {
+        let record = match extension {
+            "json" => serde_json::from_str(record).ok(),
+            _ => xml_to_json(record).ok(),
+        };
+
+        match record.as_ref().and_then(restore::record_id) {
+            Some(id) => format!("{}.{extension}", path_template::encode_segment(&id)),
+            None => format!("{}.{extension}", path_template::encode_segment(name)),
+        }
+    }
+
+    /// Everything a spawned task needs to send requests on behalf of the service
+    #[derive(Clone)]
+    struct RequestContext {
+        client: Client,
+        jps_session: Arc<Session>,
+        retry_policy: RetryPolicy,
+    }
+
+    impl RequestContext {
+        /// Saves the record at the URL to `<dir>/<name>.xml`, or .json for the Jamf Pro API.
+        /// Failures to fetch the record are returned as they are, while failures to save it are
+        /// returned as a `JamfError::Backup`
+        async fn back_up(
+            &self,
+            url: &str,
+            dir: &Path,
+            name: &str,
+            is_jamf_pro_api: bool,
+        ) -> Result<(), JamfError> {
+            let (accept_type, extension) = backup_format(is_jamf_pro_api);
+            let (_, record) = self
+                .send(Method::GET, url.to_string(), accept_type, None)
+                .await?;
+
+            let path = dir.join(backup_file_name(&record, name, extension));
+            fs::create_dir_all(dir)
+                .and_then(|_| fs::write(&path, record))
+                .map_err(|err| JamfError::Backup {
+                    status: None,
+                    reason: format!("Couldn't write {}: {err}", path.display()),
+                })
+        }
+
+        async fn send(
+            &self,
+            method: Method,
+            url: String,
+            accept_type: &str,
+            body: Option<&RequestBody>,
+        ) -> Result<(StatusCode, String), JamfError> {
+            let mut token = self.jps_session.bearer_token(&self.client).await?;
+            let mut reauthenticated = false;
+            let mut attempt = 1;
+
+            loop {
+                let can_retry = attempt < self.retry_policy.max_attempts
+                    && self.retry_policy.applies_to(&method);
+
+                let mut req_builder = self
+                    .client
+                    .request(method.clone(), &url)
+                    .bearer_auth(&token)
+                    .header("accept", accept_type);
+
+                if let Some(body) = body {
+                    req_builder = req_builder
+                        .header(CONTENT_TYPE, body.content_type)
+                        .body(body.content.clone());
+                }
+
+                let res = match req_builder.send().await {
+                    Ok(res) => res,
+                    Err(err) if can_retry && (err.is_connect() || err.is_timeout()) => {
+                        sleep(self.retry_policy.delay(attempt, None)).await;
+                        attempt += 1;
+                        continue;
+                    }
+                    Err(err) => return Err(err.into()),
+                };
+
+                // The token may have been invalidated server side before its expiration, so
+                // re-authenticate and try once more before giving up
+                if res.status() == StatusCode::UNAUTHORIZED && !reauthenticated {
+                    token = self
+                        .jps_session
+                        .renew_rejected_token(&self.client, &token)
+                        .await?;
+                    reauthenticated = true;
+                    continue;
+                }
+
+                if can_retry && RetryPolicy::is_retryable_status(res.status()) {
+                    let delay = self
+                        .retry_policy
+                        .delay(attempt, retry::retry_after(res.headers()));
+                    sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
+
+                return if res.status().is_success() {
+                    Ok((res.status(), res.text().await?))
+                } else if res.status() == StatusCode::UNAUTHORIZED {
+                    Err(JamfError::Auth {
+                        status: res.status(),
+                        reason: format!("Token rejected for {}", res.url().path()),
+                    })
+                } else {
+                    Err(JamfError::Http {
+                        status: res.status(),
+                        url: res.url().path().to_string(),
+                    })
+                };
+            }
+        }
+    }
+
+    #[derive(Clone)]
     struct UrlBuilder {
         address: String,
         api_details: CommandDetails,
@@ -174,45 +779,339 @@ pub mod api_service {
                 arg_index: 0,
             }
         }
+
+        fn url<'a>(
+            &self,
+            template: &str,
+            values: impl IntoIterator<Item = (&'a str, &'a str)>,
+        ) -> Result<String, JamfError> {
+            let mut url = format!(
+                "{}{}",
+                self.address,
+                path_template::render(template, values)?
+            );
+            if !self.api_details.subset.is_empty() {
+                url = format!("{url}/subset/{}", self.api_details.subset.join("&"));
+            }
+
+            Ok(url)
+        }
     }
 
     impl Iterator for UrlBuilder {
-        type Item = String;
+        /// The id, name, etc. a URL was built for along with the URL
+        type Item = (Option<String>, Result<String, JamfError>);
 
         fn next(&mut self) -> Option<Self::Item> {
-            match &self.api_details.args {
-                Args::None => {
-                    if self.arg_index == 0 {
-                        self.arg_index += 1;
-                        Some(format!("{}{}", self.address, self.api_details.endpoint.url))
-                    } else {
-                        None
-                    }
+            let template = self.api_details.endpoint.url;
+            let (arg, url) = match &self.api_details.args {
+                Args::None | Args::Pages(_) if self.arg_index == 0 => {
+                    (None, self.url(template, []))
                 }
-                Args::Strings(args) => {
-                    if self.arg_index == 0 {
-                        self.arg_index += 1;
-                        let mut url = format!("{}{}", self.address, self.api_details.endpoint.url);
-                        for (placeholder, arg) in args {
-                            url = url.replace(placeholder, arg);
-                        }
-
-                        Some(url)
-                    } else {
-                        None
-                    }
+                Args::Strings(args) if self.arg_index == 0 => (
+                    None,
+                    self.url(template, args.iter().map(|(k, v)| (*k, v.as_str()))),
+                ),
+                Args::Lookup(identifier, values) if self.arg_index < values.len() => {
+                    let template = template.replace(
+                        "/id/{id}",
+                        &format!("/{}/{{id}}", identifier.path_segment()),
+                    );
+                    let value = &values[self.arg_index];
+                    (
+                        Some(value.clone()),
+                        self.url(&template, [("id", value.as_str())]),
+                    )
                 }
-                Args::Ids(ids) => {
-                    if self.arg_index < ids.len() {
-                        let url = format!("{}{}", self.address, self.api_details.endpoint.url)
-                            .replace("{id}", ids.get(self.arg_index).unwrap());
-                        self.arg_index += 1;
-                        Some(url)
-                    } else {
-                        None
-                    }
+                Args::Ids(ids) if self.arg_index < ids.len() => {
+                    let id = &ids[self.arg_index];
+                    (Some(id.clone()), self.url(template, [("id", id.as_str())]))
                 }
-            }
+                _ => return None,
+            };
+
+            self.arg_index += 1;
+            Some((arg, url))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::api_endpoints::{ApiEndpoints, Args, CommandDetails, Identifier, RequestBody};
+        use super::{backup_file_name, AuthMode, JamfApiService, JamfError, UrlBuilder};
+        use reqwest::{Method, StatusCode};
+        use std::path::PathBuf;
+
+        #[test]
+        fn test_names_percent_encoded() {
+            let urls: Vec<String> = UrlBuilder::new(
+                String::from("https://test.jamfcloud.com:443"),
+                CommandDetails {
+                    args: Args::Lookup(
+                        Identifier::Name,
+                        vec![String::from("Install Chrome"), String::from("Jane's/Mac?")],
+                    ),
+                    endpoint: ApiEndpoints::PolicyShow.usage(),
+                    body: None,
+                    subset: Vec::new(),
+                },
+            )
+            .map(|(_, url)| url)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+            assert_eq!(
+                urls,
+                vec![
+                    "https://test.jamfcloud.com:443/JSSResource/policies/name/Install%20Chrome",
+                    "https://test.jamfcloud.com:443/JSSResource/policies/name/Jane%27s%2FMac%3F"
+                ]
+            );
+        }
+
+        #[test]
+        fn test_device_identifiers() {
+            let urls: Vec<String> = UrlBuilder::new(
+                String::from("https://test.jamfcloud.com:443"),
+                CommandDetails {
+                    args: Args::Lookup(
+                        Identifier::MacAddress,
+                        vec![String::from("00:1A:2B:3C:4D:5E")],
+                    ),
+                    endpoint: ApiEndpoints::MobileDelete.usage(),
+                    body: None,
+                    subset: Vec::new(),
+                },
+            )
+            .map(|(_, url)| url)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+            assert_eq!(
+                urls,
+                vec!["https://test.jamfcloud.com:443/JSSResource/mobiledevices/macaddress/00%3A1A%3A2B%3A3C%3A4D%3A5E"]
+            );
+        }
+
+        #[test]
+        fn test_subset() {
+            let endpoint = ApiEndpoints::ComputerShow.usage();
+            assert_eq!(endpoint.subset("hardware"), Some("Hardware"));
+            assert_eq!(endpoint.subset("Apps"), None);
+            assert!(ApiEndpoints::ComputerDelete.usage().subsets().is_empty());
+            assert!(ApiEndpoints::UserShow.usage().subsets().is_empty());
+
+            let urls: Vec<String> = UrlBuilder::new(
+                String::from("https://test.jamfcloud.com:443"),
+                CommandDetails {
+                    args: Args::Ids(vec![String::from("1"), String::from("2")]),
+                    endpoint,
+                    body: None,
+                    subset: vec!["General", "Hardware"],
+                },
+            )
+            .map(|(_, url)| url)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+            assert_eq!(
+                urls,
+                vec![
+                    "https://test.jamfcloud.com:443/JSSResource/computers/id/1/subset/General&Hardware",
+                    "https://test.jamfcloud.com:443/JSSResource/computers/id/2/subset/General&Hardware"
+                ]
+            );
+        }
+
+        #[test]
+        fn test_supported_identifiers() {
+            let name = Identifier::Name;
+            assert!(ApiEndpoints::PolicyDelete.usage().supports_identifier(name));
+            assert!(ApiEndpoints::GroupComputerShow
+                .usage()
+                .supports_identifier(name));
+            assert!(!ApiEndpoints::PolicyList.usage().supports_identifier(name));
+            assert!(!ApiEndpoints::PatchPolicyShow
+                .usage()
+                .supports_identifier(name));
+            assert!(!ApiEndpoints::ProScriptShow
+                .usage()
+                .supports_identifier(name));
+
+            let serial = Identifier::SerialNumber;
+            assert!(ApiEndpoints::ComputerUpdate
+                .usage()
+                .supports_identifier(serial));
+            assert!(ApiEndpoints::MobileShow.usage().supports_identifier(serial));
+            assert!(!ApiEndpoints::PolicyShow.usage().supports_identifier(serial));
+            assert!(!ApiEndpoints::ProComputerShow
+                .usage()
+                .supports_identifier(serial));
+        }
+
+        #[test]
+        fn test_lookup_not_found() {
+            let err = JamfApiService::lookup_not_found(
+                JamfError::Http {
+                    status: StatusCode::NOT_FOUND,
+                    url: String::from("/JSSResource/policies/name/Install%20Chrome"),
+                },
+                Identifier::Name,
+            );
+            assert_eq!(
+                err.to_string(),
+                "No record named 'Install Chrome' in policies"
+            );
+
+            let err = JamfApiService::lookup_not_found(
+                JamfError::Http {
+                    status: StatusCode::NOT_FOUND,
+                    url: String::from("/JSSResource/computers/serialnumber/C02X"),
+                },
+                Identifier::SerialNumber,
+            );
+            assert_eq!(
+                err.to_string(),
+                "No record with serial number 'C02X' in computers"
+            );
+
+            let err = JamfError::Http {
+                status: StatusCode::NOT_FOUND,
+                url: String::from("/JSSResource/policies/id/3"),
+            };
+            assert_eq!(
+                JamfApiService::lookup_not_found(err.clone(), Identifier::Name),
+                err
+            );
+        }
+
+        fn test_service() -> JamfApiService {
+            JamfApiService::new(
+                String::from("https://test.jamfcloud.com"),
+                None,
+                AuthMode::Basic {
+                    username: String::from("admin"),
+                    password: String::from("secret"),
+                },
+                false,
+                true,
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn test_plan() {
+            let mut service = test_service();
+            assert!(service.plan().is_empty());
+
+            service
+                .set_commands(CommandDetails {
+                    args: Args::Ids(vec![String::from("1"), String::from("{2}")]),
+                    endpoint: ApiEndpoints::PolicyUpdate.usage(),
+                    body: Some(RequestBody::new(String::from("<policy/>"))),
+                    subset: Vec::new(),
+                })
+                .unwrap();
+            let plan = service.plan();
+
+            assert_eq!(plan.len(), 2);
+            assert_eq!(plan[0].method, Method::PUT);
+            assert_eq!(
+                plan[0].url.as_deref().unwrap(),
+                "https://test.jamfcloud.com:443/JSSResource/policies/id/1"
+            );
+            assert_eq!(
+                plan[0].headers,
+                vec![
+                    ("Authorization", String::from("Bearer <redacted>")),
+                    ("Accept", String::from("application/json")),
+                    ("Content-Type", String::from("application/xml")),
+                ]
+            );
+            assert_eq!(plan[0].body.as_ref().unwrap().content, "<policy/>");
+            assert_eq!(plan[1].arg.as_deref(), Some("{2}"));
+            assert_eq!(
+                plan[1].url.as_deref().unwrap(),
+                "https://test.jamfcloud.com:443/JSSResource/policies/id/%7B2%7D"
+            );
+        }
+
+        #[test]
+        fn test_plan_backups() {
+            let mut service = test_service();
+            service.set_backup_dir(Some(PathBuf::from("backups")));
+            service
+                .set_commands(CommandDetails {
+                    args: Args::Ids(vec![String::from("1"), String::from("2")]),
+                    endpoint: ApiEndpoints::ScriptDelete.usage(),
+                    body: None,
+                    subset: Vec::new(),
+                })
+                .unwrap();
+
+            let plan: Vec<(usize, Method, String)> = service
+                .plan()
+                .into_iter()
+                .map(|request| (request.index, request.method, request.headers[1].1.clone()))
+                .collect();
+            assert_eq!(
+                plan,
+                vec![
+                    (0, Method::GET, String::from("application/xml")),
+                    (0, Method::DELETE, String::from("application/json")),
+                    (1, Method::GET, String::from("application/xml")),
+                    (1, Method::DELETE, String::from("application/json")),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_entity() {
+            assert_eq!(ApiEndpoints::ComputerDelete.usage().entity(), "computers");
+            assert_eq!(ApiEndpoints::PolicyList.usage().entity(), "policies");
+            assert_eq!(
+                ApiEndpoints::GroupComputerShow.usage().entity(),
+                "computergroups"
+            );
+            assert_eq!(ApiEndpoints::ProScriptShow.usage().entity(), "scripts");
+            assert_eq!(
+                ApiEndpoints::ProComputerList.usage().entity(),
+                "computers-inventory"
+            );
+            assert_eq!(
+                ApiEndpoints::ProMobileShow.usage().entity(),
+                "mobile-devices"
+            );
+            assert_eq!(
+                ApiEndpoints::ProMobileUpdate.usage().entity(),
+                "mobile-devices"
+            );
+        }
+
+        #[test]
+        fn test_backup_file_name() {
+            // Records deleted by name or serial number are still saved after their id
+            let computer = "<computer><general><id>12</id><name>Foo Bar</name>\
+                <serial_number>C02XL0GZJGH5</serial_number></general></computer>";
+            assert_eq!(backup_file_name(computer, "Foo Bar", "xml"), "12.xml");
+            assert_eq!(backup_file_name(computer, "C02XL0GZJGH5", "xml"), "12.xml");
+
+            let script = "<script><id>3</id><name>Clean up</name></script>";
+            assert_eq!(backup_file_name(script, "Clean up", "xml"), "3.xml");
+            assert_eq!(
+                backup_file_name(r#"{"id": "7", "name": "Foo Bar"}"#, "7", "json"),
+                "7.json"
+            );
+
+            // Without an id, the record is saved after what it was looked up by
+            assert_eq!(
+                backup_file_name(
+                    "<computer><name>Foo Bar</name></computer>",
+                    "Foo Bar",
+                    "xml"
+                ),
+                "Foo%20Bar.xml"
+            );
         }
     }
 }